@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// A boolean variable.
+pub type Var = u32;
+
+/// A reference to a reduced, ordered binary decision diagram in a [`Manager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bdd(u32);
+
+impl Bdd {
+    /// The constant false function.
+    pub const FALSE: Bdd = Bdd(0);
+    /// The constant true function.
+    pub const TRUE: Bdd = Bdd(1);
+
+    /// Check if this is one of the two constant functions.
+    pub fn is_const(self) -> bool {
+        self == Self::FALSE || self == Self::TRUE
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Node {
+    var: Var,
+    lo: Bdd,
+    hi: Bdd,
+}
+
+/// The owner of a shared collection of BDD nodes.
+///
+/// Variables are ordered by their index; smaller variables are closer to the root.
+#[derive(Debug, Clone)]
+pub struct Manager {
+    nodes: Vec<Node>,
+    unique: HashMap<Node, Bdd>,
+    ite_cache: HashMap<(Bdd, Bdd, Bdd), Bdd>,
+}
+
+impl Default for Manager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Manager {
+    /// Create a manager holding only the constant functions.
+    pub fn new() -> Self {
+        let terminal = |b| Node {
+            var: Var::MAX,
+            lo: b,
+            hi: b,
+        };
+        Self {
+            nodes: vec![terminal(Bdd::FALSE), terminal(Bdd::TRUE)],
+            unique: HashMap::new(),
+            ite_cache: HashMap::new(),
+        }
+    }
+
+    /// The function that is true exactly when `var` is.
+    pub fn var(&mut self, var: Var) -> Bdd {
+        self.mk(var, Bdd::FALSE, Bdd::TRUE)
+    }
+
+    pub fn not(&mut self, f: Bdd) -> Bdd {
+        self.ite(f, Bdd::FALSE, Bdd::TRUE)
+    }
+
+    pub fn and(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        self.ite(f, g, Bdd::FALSE)
+    }
+
+    pub fn or(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        self.ite(f, Bdd::TRUE, g)
+    }
+
+    pub fn xor(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        let ng = self.not(g);
+        self.ite(f, ng, g)
+    }
+
+    pub fn imp(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        self.ite(f, g, Bdd::TRUE)
+    }
+
+    pub fn equiv(&mut self, f: Bdd, g: Bdd) -> Bdd {
+        let ng = self.not(g);
+        self.ite(f, g, ng)
+    }
+
+    /// The conjunction of all of `fs`.
+    pub fn and_all(&mut self, fs: impl IntoIterator<Item = Bdd>) -> Bdd {
+        fs.into_iter().fold(Bdd::TRUE, |acc, f| self.and(acc, f))
+    }
+
+    /// The disjunction of all of `fs`.
+    pub fn or_all(&mut self, fs: impl IntoIterator<Item = Bdd>) -> Bdd {
+        fs.into_iter().fold(Bdd::FALSE, |acc, f| self.or(acc, f))
+    }
+
+    /// If-then-else: the function that is `g` where `f` holds and `h` elsewhere.
+    pub fn ite(&mut self, f: Bdd, g: Bdd, h: Bdd) -> Bdd {
+        // terminal cases.
+        if f == Bdd::TRUE {
+            return g;
+        }
+        if f == Bdd::FALSE {
+            return h;
+        }
+        if g == h {
+            return g;
+        }
+        if g == Bdd::TRUE && h == Bdd::FALSE {
+            return f;
+        }
+
+        if let Some(&r) = self.ite_cache.get(&(f, g, h)) {
+            return r;
+        }
+
+        // split on the smallest top variable.
+        let var = self.top(f).min(self.top(g)).min(self.top(h));
+        let (f0, f1) = self.cofactors(f, var);
+        let (g0, g1) = self.cofactors(g, var);
+        let (h0, h1) = self.cofactors(h, var);
+
+        let lo = self.ite(f0, g0, h0);
+        let hi = self.ite(f1, g1, h1);
+        let r = self.mk(var, lo, hi);
+
+        self.ite_cache.insert((f, g, h), r);
+        r
+    }
+
+    /// Substitute the constant `val` for `var`.
+    pub fn restrict(&mut self, f: Bdd, var: Var, val: bool) -> Bdd {
+        let mut memo = HashMap::new();
+        self.restrict_memo(f, var, val, &mut memo)
+    }
+
+    fn restrict_memo(&mut self, f: Bdd, var: Var, val: bool, memo: &mut HashMap<Bdd, Bdd>) -> Bdd {
+        let node = self.node(f);
+        if f.is_const() || node.var > var {
+            return f;
+        }
+        if node.var == var {
+            return if val { node.hi } else { node.lo };
+        }
+        if let Some(&r) = memo.get(&f) {
+            return r;
+        }
+
+        let lo = self.restrict_memo(node.lo, var, val, memo);
+        let hi = self.restrict_memo(node.hi, var, val, memo);
+        let r = self.mk(node.var, lo, hi);
+        memo.insert(f, r);
+        r
+    }
+
+    /// Existentially quantify `vars` away.
+    pub fn exists(&mut self, f: Bdd, vars: &BTreeSet<Var>) -> Bdd {
+        let mut memo = HashMap::new();
+        self.exists_memo(f, vars, &mut memo)
+    }
+
+    fn exists_memo(&mut self, f: Bdd, vars: &BTreeSet<Var>, memo: &mut HashMap<Bdd, Bdd>) -> Bdd {
+        if f.is_const() {
+            return f;
+        }
+        if let Some(&r) = memo.get(&f) {
+            return r;
+        }
+
+        let node = self.node(f);
+        let lo = self.exists_memo(node.lo, vars, memo);
+        let hi = self.exists_memo(node.hi, vars, memo);
+        let r = if vars.contains(&node.var) {
+            self.or(lo, hi)
+        } else {
+            self.mk(node.var, lo, hi)
+        };
+        memo.insert(f, r);
+        r
+    }
+
+    /// Universally quantify `vars` away.
+    pub fn forall(&mut self, f: Bdd, vars: &BTreeSet<Var>) -> Bdd {
+        let nf = self.not(f);
+        let ex = self.exists(nf, vars);
+        self.not(ex)
+    }
+
+    /// Evaluate `f` under the assignment `val`.
+    pub fn eval(&self, f: Bdd, val: impl Fn(Var) -> bool) -> bool {
+        let mut f = f;
+        while !f.is_const() {
+            let node = self.node(f);
+            f = if val(node.var) { node.hi } else { node.lo };
+        }
+        f == Bdd::TRUE
+    }
+
+    /// Find an assignment satisfying `f`, if one exists.
+    ///
+    /// Variables that `f` does not depend on along the chosen path are left out.
+    pub fn any_sat(&self, f: Bdd) -> Option<BTreeMap<Var, bool>> {
+        if f == Bdd::FALSE {
+            return None;
+        }
+
+        let mut assignment = BTreeMap::new();
+        let mut f = f;
+        while !f.is_const() {
+            let node = self.node(f);
+            // every non-false node has a path to true; prefer the low branch.
+            if node.lo != Bdd::FALSE {
+                assignment.insert(node.var, false);
+                f = node.lo;
+            } else {
+                assignment.insert(node.var, true);
+                f = node.hi;
+            }
+        }
+        Some(assignment)
+    }
+
+    fn mk(&mut self, var: Var, lo: Bdd, hi: Bdd) -> Bdd {
+        if lo == hi {
+            return lo;
+        }
+
+        let node = Node { var, lo, hi };
+        if let Some(&b) = self.unique.get(&node) {
+            return b;
+        }
+
+        let b = Bdd(self.nodes.len() as u32);
+        self.nodes.push(node);
+        self.unique.insert(node, b);
+        b
+    }
+
+    fn node(&self, f: Bdd) -> Node {
+        self.nodes[f.0 as usize]
+    }
+
+    fn top(&self, f: Bdd) -> Var {
+        self.node(f).var
+    }
+
+    fn cofactors(&self, f: Bdd, var: Var) -> (Bdd, Bdd) {
+        let node = self.node(f);
+        if node.var == var {
+            (node.lo, node.hi)
+        } else {
+            (f, f)
+        }
+    }
+}
@@ -0,0 +1,743 @@
+//! A symbolic model checker for knowledge structures, in the style of SMCDEL.
+//!
+//! A knowledge structure consists of a vocabulary of propositions, a boolean law that restricts
+//! the possible states (valuations of the vocabulary), and for each agent the set of propositions
+//! it observes. Two states are indistinguishable to an agent exactly when they agree on the
+//! propositions it observes. All of these are represented as binary decision diagrams.
+//!
+//! Agents may additionally be given a plausibility law, the states they consider most likely.
+//! An agent believes what is true at the plausible states it cannot distinguish from the actual
+//! one, or, if there are none, what it knows. This gives a KD45 modality that may be wrong.
+
+pub mod bdd;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+use epistemic::{ActionModel, Announced, Counterexample, Form, KnowStruct, Semantics, Verdict};
+
+use bdd::{Bdd, Manager, Var};
+
+/// A knowledge structure backed by binary decision diagrams.
+///
+/// A formula is satisfied when it is true at every state of the structure.
+#[derive(Debug)]
+pub struct BddKnowStruct<A, P> {
+    manager: RefCell<Manager>,
+    props: RefCell<BTreeMap<P, Var>>,
+    law: Bdd,
+    obs: BTreeMap<A, BTreeSet<Var>>,
+    beliefs: BTreeMap<A, Bdd>,
+}
+
+/// A knowledge structure during evaluation, possibly extended with fresh propositions.
+#[derive(Debug, Clone)]
+struct Structure<A> {
+    vocab: BTreeSet<Var>,
+    law: Bdd,
+    obs: BTreeMap<A, BTreeSet<Var>>,
+    beliefs: BTreeMap<A, Bdd>,
+    next: Var,
+}
+
+impl<A, P> BddKnowStruct<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    /// Let `agent` consider the states at which `plausible` is true most likely.
+    ///
+    /// Without a plausibility law, the beliefs of an agent coincide with its knowledge.
+    pub fn with_belief(mut self, agent: A, plausible: Form<A, P>) -> Self {
+        // the plausibility law is interpreted over the initial structure.
+        let st = self.structure(&plausible);
+        let b = {
+            let mut man = self.manager.borrow_mut();
+            self.bdd_of(&mut man, &st, &plausible)
+        };
+
+        self.beliefs.insert(agent, b);
+        self
+    }
+}
+
+impl<A, P> BddKnowStruct<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    /// Get the variable for `prop`, allocating a fresh one if it has not been seen.
+    fn var(&self, prop: &P) -> Var {
+        let mut props = self.props.borrow_mut();
+        let next = props.len() as Var;
+        *props.entry(prop.clone()).or_insert(next)
+    }
+
+    /// The structure as given, extended with the propositions of `form`.
+    ///
+    /// The binders of quantifiers are registered too, so that the fresh propositions of
+    /// announcements and updates never share their variables.
+    fn structure(&self, form: &Form<A, P>) -> Structure<A> {
+        for prop in form.vocab().chain(form.binders()) {
+            self.var(prop);
+        }
+
+        let props = self.props.borrow();
+        Structure {
+            vocab: props.values().copied().collect(),
+            law: self.law,
+            obs: self.obs.clone(),
+            beliefs: self.beliefs.clone(),
+            next: props.len() as Var,
+        }
+    }
+
+    /// Compute the BDD of the states of `st` at which `form` is true.
+    fn bdd_of(&self, man: &mut Manager, st: &Structure<A>, form: &Form<A, P>) -> Bdd {
+        match form {
+            Form::Top => Bdd::TRUE,
+            Form::Bot => Bdd::FALSE,
+            Form::Prop(p) => {
+                let var = self.var(p);
+                man.var(var)
+            }
+            Form::Neg(p) => {
+                let b = self.bdd_of(man, st, p);
+                man.not(b)
+            }
+            Form::Conj(ps) => {
+                let bs: Vec<_> = ps.iter().map(|p| self.bdd_of(man, st, p)).collect();
+                man.and_all(bs)
+            }
+            Form::Disj(ps) => {
+                let bs: Vec<_> = ps.iter().map(|p| self.bdd_of(man, st, p)).collect();
+                man.or_all(bs)
+            }
+            Form::Xor(ps) => {
+                let bs: Vec<_> = ps.iter().map(|p| self.bdd_of(man, st, p)).collect();
+                bs.into_iter().fold(Bdd::FALSE, |acc, b| man.xor(acc, b))
+            }
+            Form::Impl(p1, p2) => {
+                let b1 = self.bdd_of(man, st, p1);
+                let b2 = self.bdd_of(man, st, p2);
+                man.imp(b1, b2)
+            }
+            Form::Equiv(p1, p2) => {
+                let b1 = self.bdd_of(man, st, p1);
+                let b2 = self.bdd_of(man, st, p2);
+                man.equiv(b1, b2)
+            }
+            Form::Forall(xs, p) => {
+                let vars = xs.iter().map(|x| self.var(x)).collect();
+                let b = self.bdd_of(man, st, p);
+                man.forall(b, &vars)
+            }
+            Form::Exist(xs, p) => {
+                let vars = xs.iter().map(|x| self.var(x)).collect();
+                let b = self.bdd_of(man, st, p);
+                man.exists(b, &vars)
+            }
+            Form::K(ag, p) => {
+                let b = self.bdd_of(man, st, p);
+                st.know(man, [ag], b)
+            }
+            Form::Kw(ag, p) => {
+                let b = self.bdd_of(man, st, p);
+                let nb = man.not(b);
+                let yes = st.know(man, [ag], b);
+                let no = st.know(man, [ag], nb);
+                man.or(yes, no)
+            }
+            Form::B(ag, p) => {
+                let b = self.bdd_of(man, st, p);
+                st.believe(man, ag, b)
+            }
+            Form::CK(ags, p) => {
+                let b = self.bdd_of(man, st, p);
+                st.common(man, ags, b)
+            }
+            Form::DK(ags, p) => {
+                let b = self.bdd_of(man, st, p);
+                st.know(man, ags, b)
+            }
+            Form::CKw(ags, p) => {
+                let b = self.bdd_of(man, st, p);
+                let nb = man.not(b);
+                let yes = st.common(man, ags, b);
+                let no = st.common(man, ags, nb);
+                man.or(yes, no)
+            }
+            Form::DKw(ags, p) => {
+                let b = self.bdd_of(man, st, p);
+                let nb = man.not(b);
+                let yes = st.know(man, ags, b);
+                let no = st.know(man, ags, nb);
+                man.or(yes, no)
+            }
+            Form::PA(p1, p2) => {
+                let b1 = self.bdd_of(man, st, p1);
+                let st = st.public(man, b1);
+                let b2 = self.bdd_of(man, &st, p2);
+                man.imp(b1, b2)
+            }
+            Form::PAw(p1, p2) => {
+                let b1 = self.bdd_of(man, st, p1);
+                let nb1 = man.not(b1);
+                let (st_yes, st_no) = (st.public(man, b1), st.public(man, nb1));
+                let yes = self.bdd_of(man, &st_yes, p2);
+                let no = self.bdd_of(man, &st_no, p2);
+                man.ite(b1, yes, no)
+            }
+            Form::GA(ags, p1, p2) => {
+                let b1 = self.bdd_of(man, st, p1);
+                let (st, k) = st.group(man, ags, b1);
+                let b2 = self.bdd_of(man, &st, p2);
+                let yes = man.restrict(b2, k, true);
+                man.imp(b1, yes)
+            }
+            Form::GAw(ags, p1, p2) => {
+                let b1 = self.bdd_of(man, st, p1);
+                let (st, k) = st.group(man, ags, b1);
+                let b2 = self.bdd_of(man, &st, p2);
+                let yes = man.restrict(b2, k, true);
+                let no = man.restrict(b2, k, false);
+                man.ite(b1, yes, no)
+            }
+            Form::Update(action, events, p) => {
+                let pre: Vec<_> = action
+                    .events()
+                    .map(|e| self.bdd_of(man, st, action.pre(e)))
+                    .collect();
+                let (st, points) = st.update(man, action, &pre);
+                let b = self.bdd_of(man, &st, p);
+                let after: Vec<_> = events
+                    .iter()
+                    .map(|&e| {
+                        let after = points[e]
+                            .iter()
+                            .fold(b, |b, (&k, &val)| man.restrict(b, k, val));
+                        man.imp(pre[e], after)
+                    })
+                    .collect();
+                man.and_all(after)
+            }
+        }
+    }
+}
+
+impl<A, P> BddKnowStruct<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    /// Follow the announcements of `form` from `state`, at which it is false, to the point at
+    /// which it is falsified.
+    fn counterexample(
+        &self,
+        man: &mut Manager,
+        st: Structure<A>,
+        state: BTreeMap<Var, bool>,
+        form: &Form<A, P>,
+    ) -> Counterexample<A, P> {
+        let (mut st, mut state, mut form) = (st, state, form);
+        let mut announcements = Vec::new();
+        loop {
+            let (group, pre, post) = match form {
+                Form::PA(p1, p2) | Form::PAw(p1, p2) => (None, p1, p2),
+                Form::GA(ags, p1, p2) | Form::GAw(ags, p1, p2) => (Some(ags), p1, p2),
+                _ => break,
+            };
+
+            let b = self.bdd_of(man, &st, pre);
+            let truth = man.eval(b, |var| state.get(&var).copied().unwrap_or(false));
+            st = match group {
+                None => {
+                    let b = if truth { b } else { man.not(b) };
+                    st.public(man, b)
+                }
+                Some(ags) => {
+                    let (st, k) = st.group(man, ags, b);
+                    state.insert(k, truth);
+                    st
+                }
+            };
+
+            announcements.push(Announced {
+                group: group.cloned(),
+                form: (**pre).clone(),
+                truth,
+            });
+            form = post;
+        }
+
+        let world = self
+            .props
+            .borrow()
+            .iter()
+            .map(|(p, var)| (p.clone(), state.get(var).copied().unwrap_or(false)))
+            .collect();
+        Counterexample {
+            world,
+            announcements,
+        }
+    }
+}
+
+impl<A> Structure<A>
+where
+    A: Ord + Clone,
+{
+    /// The propositions that none of `ags` observe.
+    fn unobserved<'a>(&self, ags: impl IntoIterator<Item = &'a A>) -> BTreeSet<Var>
+    where
+        A: 'a,
+    {
+        let observed: BTreeSet<_> = ags
+            .into_iter()
+            .filter_map(|ag| self.obs.get(ag))
+            .flatten()
+            .collect();
+        self.vocab
+            .iter()
+            .filter(|var| !observed.contains(var))
+            .copied()
+            .collect()
+    }
+
+    /// The (distributed) knowledge of `b` amongst `ags`.
+    fn know<'a>(&self, man: &mut Manager, ags: impl IntoIterator<Item = &'a A>, b: Bdd) -> Bdd
+    where
+        A: 'a,
+    {
+        let others = self.unobserved(ags);
+        let imp = man.imp(self.law, b);
+        man.forall(imp, &others)
+    }
+
+    /// The belief of `b` by `ag`: `b` is true at the plausible states `ag` considers possible, or
+    /// at all of them if none are plausible.
+    fn believe(&self, man: &mut Manager, ag: &A, b: Bdd) -> Bdd {
+        let known = self.know(man, [ag], b);
+        let Some(&plausible) = self.beliefs.get(ag) else {
+            return known;
+        };
+
+        let others = self.unobserved([ag]);
+        let pl = man.and(self.law, plausible);
+        let any = man.exists(pl, &others);
+        let imp = man.imp(pl, b);
+        let believed = man.forall(imp, &others);
+        man.ite(any, believed, known)
+    }
+
+    /// The common knowledge of `b` amongst `ags`, as a greatest fixpoint.
+    fn common(&self, man: &mut Manager, ags: &[A], b: Bdd) -> Bdd {
+        let mut z = Bdd::TRUE;
+        loop {
+            let knows: Vec<_> = ags.iter().map(|ag| self.know(man, [ag], z)).collect();
+            let next = man.and_all(knows);
+            let next = man.and(b, next);
+            if next == z {
+                return z;
+            }
+            z = next;
+        }
+    }
+
+    /// The structure after the public announcement of `b`.
+    fn public(&self, man: &mut Manager, b: Bdd) -> Self {
+        Self {
+            law: man.and(self.law, b),
+            ..self.clone()
+        }
+    }
+
+    /// The structure after the announcement of `b` to `ags`, and the fresh proposition recording
+    /// the announcement.
+    fn group(&self, man: &mut Manager, ags: &[A], b: Bdd) -> (Self, Var) {
+        let k = self.next;
+
+        let mut st = self.clone();
+        st.next += 1;
+        st.vocab.insert(k);
+
+        let kb = man.var(k);
+        let equiv = man.equiv(kb, b);
+        st.law = man.and(st.law, equiv);
+
+        for ag in ags {
+            st.obs.entry(ag.clone()).or_default().insert(k);
+        }
+
+        (st, k)
+    }
+
+    /// The structure after the product update with `action`, whose events have the preconditions
+    /// `pre`, and for each event the values of the fresh propositions recording it.
+    ///
+    /// Each event is recorded by a proposition of its own, and each class of events of an agent
+    /// by a proposition that the agent observes.
+    fn update<P>(
+        &self,
+        man: &mut Manager,
+        action: &ActionModel<A, P>,
+        pre: &[Bdd],
+    ) -> (Self, Vec<BTreeMap<Var, bool>>) {
+        let mut st = self.clone();
+        let fresh = |st: &mut Self| {
+            let k = st.next;
+            st.next += 1;
+            st.vocab.insert(k);
+            k
+        };
+
+        let events: Vec<_> = action.events().map(|_| fresh(&mut st)).collect();
+        let mut points: Vec<BTreeMap<_, _>> = action
+            .events()
+            .map(|e| {
+                events
+                    .iter()
+                    .enumerate()
+                    .map(|(f, &k)| (k, e == f))
+                    .collect()
+            })
+            .collect();
+
+        // exactly one event happens, and its precondition is true.
+        let happens: Vec<_> = action
+            .events()
+            .map(|e| {
+                let lits: Vec<_> = points[e]
+                    .iter()
+                    .map(|(&k, &val)| {
+                        let kb = man.var(k);
+                        if val {
+                            kb
+                        } else {
+                            man.not(kb)
+                        }
+                    })
+                    .collect();
+                let cube = man.and_all(lits);
+                man.and(cube, pre[e])
+            })
+            .collect();
+        let happens = man.or_all(happens);
+        st.law = man.and(st.law, happens);
+
+        for ag in action.agents() {
+            let mut classes: Vec<_> = action.events().map(|e| action.class(ag, e)).collect();
+            classes.sort_unstable();
+            classes.dedup();
+
+            for class in classes {
+                let k = fresh(&mut st);
+                let members: Vec<_> = action
+                    .events()
+                    .filter(|&e| action.class(ag, e) == class)
+                    .map(|e| man.var(events[e]))
+                    .collect();
+                let members = man.or_all(members);
+                let kb = man.var(k);
+                let equiv = man.equiv(kb, members);
+                st.law = man.and(st.law, equiv);
+                st.obs.entry(ag.clone()).or_default().insert(k);
+
+                for e in action.events() {
+                    points[e].insert(k, action.class(ag, e) == class);
+                }
+            }
+        }
+
+        (st, points)
+    }
+}
+
+impl<A, P> Semantics for BddKnowStruct<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    type Agent = A;
+    type Prop = P;
+
+    fn check(&self, form: Form<Self::Agent, Self::Prop>) -> Verdict<Self::Agent, Self::Prop> {
+        let st = self.structure(&form);
+
+        let mut man = self.manager.borrow_mut();
+        let b = self.bdd_of(&mut man, &st, &form);
+        let nb = man.not(b);
+        let bad = man.and(st.law, nb);
+        match man.any_sat(bad) {
+            None => Verdict::Holds,
+            Some(state) => Verdict::Fails(self.counterexample(&mut man, st, state, &form)),
+        }
+    }
+}
+
+impl<A, P> KnowStruct for BddKnowStruct<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    fn new(
+        vocab: Vec<Self::Prop>,
+        law: Form<Self::Agent, Self::Prop>,
+        obs: Vec<(Self::Agent, Vec<Self::Prop>)>,
+    ) -> Self {
+        let mut this = Self {
+            manager: RefCell::new(Manager::new()),
+            props: RefCell::new(BTreeMap::new()),
+            law: Bdd::TRUE,
+            obs: BTreeMap::new(),
+            beliefs: BTreeMap::new(),
+        };
+
+        for prop in &vocab {
+            this.var(prop);
+        }
+
+        let obs = obs.into_iter().fold(
+            BTreeMap::new(),
+            |mut acc: BTreeMap<_, BTreeSet<_>>, (ag, props)| {
+                acc.entry(ag)
+                    .or_default()
+                    .extend(props.iter().map(|p| this.var(p)));
+                acc
+            },
+        );
+
+        // the law is interpreted over the trivial structure.
+        let st = this.structure(&law);
+        let law = {
+            let mut man = this.manager.borrow_mut();
+            this.bdd_of(&mut man, &st, &law)
+        };
+
+        this.law = law;
+        this.obs = obs;
+        this
+    }
+
+    fn valid(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
+        self.sat(form)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use epistemic::{ActionModel, Form, KnowStruct, Semantics};
+
+    use super::BddKnowStruct;
+
+    fn p(x: &'static str) -> Form<&'static str, &'static str> {
+        Form::Prop(x)
+    }
+
+    fn neg(p: Form<&'static str, &'static str>) -> Form<&'static str, &'static str> {
+        Form::Neg(Box::new(p))
+    }
+
+    fn know(
+        ag: &'static str,
+        p: Form<&'static str, &'static str>,
+    ) -> Form<&'static str, &'static str> {
+        Form::K(ag, Box::new(p))
+    }
+
+    fn structure() -> BddKnowStruct<&'static str, &'static str> {
+        // `a` sees `x`, `b` sees `y`.
+        BddKnowStruct::new(
+            vec!["x", "y"],
+            Form::Top,
+            vec![("a", vec!["x"]), ("b", vec!["y"])],
+        )
+    }
+
+    #[test]
+    fn test_know() {
+        let st = structure();
+
+        assert!(st.sat(Form::Disj(vec![know("a", p("x")), know("a", neg(p("x")))])));
+        assert!(st.sat(Form::Kw("a", Box::new(p("x")))));
+        assert!(!st.sat(Form::Kw("b", Box::new(p("x")))));
+        assert!(!st.sat(know("a", p("x"))));
+        assert!(st.sat(neg(know("b", p("x")))));
+        assert!(st.sat(Form::DKw(
+            vec!["a", "b"],
+            Box::new(Form::Conj(vec![p("x"), p("y")]))
+        )));
+        assert!(!st.sat(Form::CKw(vec!["a", "b"], Box::new(p("x")))));
+    }
+
+    #[test]
+    fn test_belief() {
+        // `b` does not see `x`, but finds it plausible.
+        let st = structure().with_belief("b", p("x"));
+
+        assert!(st.sat(Form::B("b", Box::new(p("x")))));
+        assert!(!st.sat(know("b", p("x"))));
+        assert!(st.sat(neg(Form::B("b", Box::new(Form::Bot)))));
+        assert!(st.sat(Form::Equiv(
+            Box::new(Form::B("a", Box::new(p("x")))),
+            Box::new(know("a", p("x")))
+        )));
+
+        // a false belief is revised once the opposite is announced.
+        assert!(st.sat(Form::PA(
+            Box::new(neg(p("x"))),
+            Box::new(Form::B("b", Box::new(neg(p("x")))))
+        )));
+    }
+
+    #[test]
+    fn test_queries() {
+        let st = structure();
+
+        assert!(st.valid(Form::Kw("a", Box::new(p("x")))));
+        assert!(!st.valid(p("x")));
+        assert!(st.satisfiable(p("x")));
+        assert!(!st.satisfiable(Form::Conj(vec![know("b", p("x")), p("y")])));
+        assert!(st.equivalent(know("a", p("x")), p("x")));
+        assert!(!st.equivalent(know("b", p("x")), p("x")));
+        assert!(st.entails(Form::Conj(vec![p("x"), p("y")]), know("b", p("y"))));
+    }
+
+    #[test]
+    fn test_reduce() {
+        let st = structure();
+
+        let action = ActionModel::new(vec![p("x"), neg(p("x")), Form::Top])
+            .with_partition("a", vec![vec![0, 1], vec![2]])
+            .with_partition("b", vec![vec![0], vec![1], vec![2]]);
+        let forms = [
+            Form::PA(Box::new(p("x")), Box::new(know("b", p("x")))),
+            Form::PAw(
+                Box::new(p("y")),
+                Box::new(Form::DKw(vec!["a", "b"], Box::new(p("y")))),
+            ),
+            Form::GA(
+                vec!["a"],
+                Box::new(p("y")),
+                Box::new(Form::Conj(vec![know("a", p("y")), neg(know("b", p("x")))])),
+            ),
+            Form::GAw(
+                vec!["b"],
+                Box::new(p("x")),
+                Box::new(Form::GAw(
+                    vec!["a"],
+                    Box::new(p("y")),
+                    Box::new(know("b", Form::Kw("a", Box::new(p("y"))))),
+                )),
+            ),
+            Form::Update(
+                Box::new(action),
+                vec![0, 1],
+                Box::new(know("a", Form::Kw("b", Box::new(p("x"))))),
+            ),
+        ];
+
+        for form in forms {
+            let reduced = form.clone().reduce_announcements().unwrap();
+            assert!(st.equivalent(form, reduced));
+        }
+    }
+
+    #[test]
+    fn test_law() {
+        let st: BddKnowStruct<&str, &str> = BddKnowStruct::new(
+            vec!["x", "y"],
+            Form::Equiv(Box::new(p("x")), Box::new(p("y"))),
+            vec![("a", vec!["x"])],
+        );
+
+        assert!(st.sat(Form::Impl(Box::new(p("x")), Box::new(know("a", p("y"))))));
+        assert!(st.sat(Form::CKw(vec!["a"], Box::new(p("y")))));
+    }
+
+    #[test]
+    fn test_announcements() {
+        let st = structure();
+
+        assert!(st.sat(Form::PA(Box::new(p("x")), Box::new(know("b", p("x"))))));
+        assert!(st.sat(Form::PAw(
+            Box::new(p("y")),
+            Box::new(Form::CKw(vec!["a", "b"], Box::new(p("y"))))
+        )));
+
+        // a private announcement to `a` tells `b` nothing new.
+        assert!(st.sat(Form::GA(
+            vec!["a"],
+            Box::new(p("y")),
+            Box::new(Form::Conj(vec![know("a", p("y")), neg(know("b", p("x")))]))
+        )));
+        assert!(st.sat(Form::GAw(
+            vec!["a", "b"],
+            Box::new(p("y")),
+            Box::new(Form::CKw(vec!["a", "b"], Box::new(p("y"))))
+        )));
+        assert!(!st.sat(Form::GAw(
+            vec!["b"],
+            Box::new(p("x")),
+            Box::new(Form::CKw(vec!["b", "c"], Box::new(p("x"))))
+        )));
+    }
+
+    #[test]
+    fn test_update() {
+        let st = structure();
+
+        // `b` learns whether `x`, and `a` only that `b` learned something.
+        let action = ActionModel::new(vec![p("x"), neg(p("x")), Form::Top])
+            .with_partition("a", vec![vec![0, 1], vec![2]])
+            .with_partition("b", vec![vec![0], vec![1], vec![2]]);
+        let update = |p| Form::Update(Box::new(action.clone()), vec![0, 1], Box::new(p));
+
+        assert!(st.sat(update(Form::Kw("b", Box::new(p("x"))))));
+        assert!(st.sat(update(know("a", Form::Kw("b", Box::new(p("x")))))));
+        assert!(st.sat(update(neg(know("a", p("y"))))));
+
+        // `a` cannot rule out that nothing happened.
+        let action = action.with_partition("a", vec![vec![0, 1, 2]]);
+        let update = |p| Form::Update(Box::new(action.clone()), vec![0, 1], Box::new(p));
+        assert!(!st.sat(update(know("a", Form::Kw("b", Box::new(p("x")))))));
+        assert!(st.sat(update(Form::Kw("b", Box::new(p("x"))))));
+    }
+
+    #[test]
+    fn test_counterexample() {
+        let st = structure();
+
+        let form = Form::GAw(
+            vec!["b"],
+            Box::new(p("x")),
+            Box::new(Form::Impl(
+                Box::new(p("y")),
+                Box::new(neg(know("b", p("x")))),
+            )),
+        );
+        let cex = st.check(form).counterexample().cloned().unwrap();
+        assert_eq!(cex.world.get("x"), Some(&true));
+        assert_eq!(cex.world.get("y"), Some(&true));
+        assert_eq!(cex.announcements.len(), 1);
+        assert_eq!(cex.announcements[0].group, Some(vec!["b"]));
+        assert!(cex.announcements[0].truth);
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        let st = structure();
+
+        assert!(st.sat(Form::Exist(vec!["x"], Box::new(p("x")))));
+        assert!(!st.sat(Form::Forall(vec!["x"], Box::new(p("x")))));
+        assert!(st.sat(Form::Forall(
+            vec!["y"],
+            Box::new(Form::Disj(vec![p("y"), neg(p("y"))]))
+        )));
+
+        // a binder that does not occur in the body is not a fresh proposition.
+        let form = |p| Form::GA(vec!["b"], Box::new(Form::Prop("x")), Box::new(p));
+        assert!(st.sat(form(know("b", p("x")))));
+        assert!(st.sat(form(Form::Forall(vec!["z"], Box::new(know("b", p("x")))))));
+    }
+}
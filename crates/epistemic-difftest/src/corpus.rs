@@ -0,0 +1,48 @@
+use epistemic::Form;
+
+use crate::{Agent, Prop, Structure};
+
+/// A formula on which the backends once disagreed, with its expected validity.
+#[derive(Debug, Clone)]
+pub struct Regression {
+    pub structure: Structure,
+    pub form: Form<Agent, Prop>,
+    pub valid: bool,
+}
+
+/// The regressions, mostly of quantifiers over modalities, at which a quantified proposition
+/// varies from state to state.
+pub fn regressions() -> Vec<Regression> {
+    let p = |x| Box::new(Form::Prop(x));
+    let know = |ag, x| Box::new(Form::K(ag, p(x)));
+
+    // agent 0 observes proposition 0, and agent 1 observes nothing.
+    let structure = Structure {
+        vocab: vec![0, 1],
+        law: Form::Top,
+        obs: vec![(0, vec![0]), (1, vec![])],
+    };
+    let case = |form, valid| Regression {
+        structure: structure.clone(),
+        form,
+        valid,
+    };
+
+    vec![
+        // a binder that occurs nowhere else is not the copy of an announcement.
+        case(
+            Form::GA(vec![1], p(0), Box::new(Form::Forall(vec![2], know(1, 0)))),
+            true,
+        ),
+        // agent 1 cannot see the value of 0 that is chosen at the actual state.
+        case(Form::Exist(vec![0], know(1, 0)), false),
+        // but agent 0 can.
+        case(Form::Exist(vec![0], know(0, 0)), true),
+        case(Form::Forall(vec![0], Box::new(Form::Kw(0, p(0)))), true),
+        case(Form::Forall(vec![1], Box::new(Form::Kw(0, p(1)))), false),
+        case(
+            Form::Exist(vec![1], Box::new(Form::CK(vec![0, 1], Box::new(Form::Top)))),
+            true,
+        ),
+    ]
+}
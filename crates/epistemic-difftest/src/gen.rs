@@ -0,0 +1,231 @@
+use epistemic::{ActionModel, Form};
+
+use crate::{Agent, Prop, Structure};
+
+/// A small deterministic pseudo-random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, for `n > 0`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// `true` with probability `1 / n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    /// A non-empty subset of `0..n`, in increasing order.
+    pub fn subset(&mut self, n: usize) -> Vec<usize> {
+        let mut xs: Vec<_> = (0..n).filter(|_| self.one_in(2)).collect();
+        if xs.is_empty() {
+            xs.push(self.below(n));
+        }
+        xs
+    }
+}
+
+/// A generator of random structures and formulas.
+#[derive(Debug, Clone)]
+pub struct Gen {
+    pub rng: Rng,
+    /// The number of propositions.
+    pub props: usize,
+    /// The number of agents.
+    pub agents: usize,
+    /// The maximum depth of formulas.
+    pub depth: usize,
+    /// Whether to generate quantifiers.
+    pub quantifiers: bool,
+    /// Whether to generate action models.
+    pub updates: bool,
+    /// Whether to generate beliefs, of agents without plausibility laws.
+    pub beliefs: bool,
+}
+
+impl Gen {
+    /// Create a generator of small structures and formulas.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            props: 3,
+            agents: 2,
+            depth: 3,
+            quantifiers: true,
+            updates: true,
+            beliefs: true,
+        }
+    }
+
+    /// Generate a structure with a boolean law, in which each agent observes some propositions.
+    pub fn structure(&mut self) -> Structure {
+        let vocab = (0..self.props).collect();
+        // a law that rules out every state makes every formula valid.
+        let law = match self.rng.one_in(2) {
+            true => Form::Top,
+            false => Form::Disj(vec![self.boolean(2), self.boolean(2)]),
+        };
+        let obs = (0..self.agents)
+            .map(|ag| {
+                let props = (0..self.props).filter(|_| self.rng.one_in(2)).collect();
+                (ag, props)
+            })
+            .collect();
+        Structure { vocab, law, obs }
+    }
+
+    /// Generate a formula without modalities.
+    pub fn boolean(&mut self, depth: usize) -> Form<Agent, Prop> {
+        if depth == 0 || self.rng.one_in(3) {
+            return Form::Prop(self.rng.below(self.props));
+        }
+        let sub = |gen: &mut Self| gen.boolean(depth - 1);
+        match self.rng.below(3) {
+            0 => Form::Neg(Box::new(sub(self))),
+            1 => Form::Conj(vec![sub(self), sub(self)]),
+            _ => Form::Disj(vec![sub(self), sub(self)]),
+        }
+    }
+
+    /// Generate a formula of at most the configured depth.
+    pub fn form(&mut self) -> Form<Agent, Prop> {
+        self.form_of(self.depth)
+    }
+
+    fn form_of(&mut self, depth: usize) -> Form<Agent, Prop> {
+        if depth == 0 || self.rng.one_in(4) {
+            return match self.rng.below(6) {
+                0 => Form::Top,
+                1 => Form::Bot,
+                _ => Form::Prop(self.rng.below(self.props)),
+            };
+        }
+
+        let d = depth - 1;
+        let ag = self.rng.below(self.agents);
+        let ags = self.rng.subset(self.agents);
+        let kinds: Vec<_> = Kind::ALL
+            .into_iter()
+            .filter(|kind| match kind {
+                Kind::Forall | Kind::Exist => self.quantifiers,
+                Kind::Update => self.updates,
+                Kind::B => self.beliefs,
+                _ => true,
+            })
+            .collect();
+
+        let sub = |gen: &mut Self| Box::new(gen.form_of(d));
+        match kinds[self.rng.below(kinds.len())] {
+            Kind::Neg => Form::Neg(sub(self)),
+            Kind::Conj => Form::Conj(self.forms(d)),
+            Kind::Disj => Form::Disj(self.forms(d)),
+            Kind::Xor => Form::Xor(self.forms(d)),
+            Kind::Impl => Form::Impl(sub(self), sub(self)),
+            Kind::Equiv => Form::Equiv(sub(self), sub(self)),
+            Kind::Forall => Form::Forall(self.rng.subset(self.props), sub(self)),
+            Kind::Exist => Form::Exist(self.rng.subset(self.props), sub(self)),
+            Kind::K => Form::K(ag, sub(self)),
+            Kind::Kw => Form::Kw(ag, sub(self)),
+            Kind::B => Form::B(ag, sub(self)),
+            Kind::CK => Form::CK(ags, sub(self)),
+            Kind::DK => Form::DK(ags, sub(self)),
+            Kind::CKw => Form::CKw(ags, sub(self)),
+            Kind::DKw => Form::DKw(ags, sub(self)),
+            Kind::PA => Form::PA(sub(self), sub(self)),
+            Kind::PAw => Form::PAw(sub(self), sub(self)),
+            Kind::GA => Form::GA(ags, sub(self), sub(self)),
+            Kind::GAw => Form::GAw(ags, sub(self), sub(self)),
+            Kind::Update => self.update(d),
+        }
+    }
+
+    /// Generate two or three formulas.
+    fn forms(&mut self, depth: usize) -> Vec<Form<Agent, Prop>> {
+        let n = 2 + self.rng.below(2);
+        (0..n).map(|_| self.form_of(depth)).collect()
+    }
+
+    /// Generate an update with an action model of up to three events.
+    fn update(&mut self, depth: usize) -> Form<Agent, Prop> {
+        let n = 1 + self.rng.below(3);
+        let pre = (0..n).map(|_| self.boolean(1)).collect();
+        let mut action = ActionModel::new(pre);
+        for ag in 0..self.agents {
+            if self.rng.one_in(3) {
+                continue;
+            }
+            let mut partition = vec![Vec::new(); n];
+            for e in 0..n {
+                partition[self.rng.below(n)].push(e);
+            }
+            partition.retain(|block| !block.is_empty());
+            action = action.with_partition(ag, partition);
+        }
+        let events = self.rng.subset(n);
+        Form::Update(Box::new(action), events, Box::new(self.form_of(depth)))
+    }
+}
+
+/// The compound formulas.
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Neg,
+    Conj,
+    Disj,
+    Xor,
+    Impl,
+    Equiv,
+    Forall,
+    Exist,
+    K,
+    Kw,
+    B,
+    CK,
+    DK,
+    CKw,
+    DKw,
+    PA,
+    PAw,
+    GA,
+    GAw,
+    Update,
+}
+
+impl Kind {
+    const ALL: [Self; 20] = [
+        Kind::Neg,
+        Kind::Conj,
+        Kind::Disj,
+        Kind::Xor,
+        Kind::Impl,
+        Kind::Equiv,
+        Kind::Forall,
+        Kind::Exist,
+        Kind::K,
+        Kind::Kw,
+        Kind::B,
+        Kind::CK,
+        Kind::DK,
+        Kind::CKw,
+        Kind::DKw,
+        Kind::PA,
+        Kind::PAw,
+        Kind::GA,
+        Kind::GAw,
+        Kind::Update,
+    ];
+}
@@ -0,0 +1,345 @@
+//! Differential testing of the [`KnowStruct`] backends.
+//!
+//! A [`Harness`] generates random small knowledge structures and formulas, checks the validity of
+//! each formula with every backend, and shrinks any disagreement to a minimal formula. The
+//! backends are those enabled by the cargo features of the same name, along with any other
+//! [`Checker`]. The [`regressions`] are formulas on which the backends once disagreed.
+
+mod corpus;
+mod gen;
+mod shrink;
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use epistemic::{Form, KnowStruct};
+
+pub use corpus::{regressions, Regression};
+pub use gen::{Gen, Rng};
+pub use shrink::{shrink, shrinks};
+
+/// The agents of generated formulas.
+pub type Agent = usize;
+
+/// The propositions of generated formulas.
+pub type Prop = usize;
+
+/// A knowledge structure, to be built by each backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Structure {
+    pub vocab: Vec<Prop>,
+    pub law: Form<Agent, Prop>,
+    pub obs: Vec<(Agent, Vec<Prop>)>,
+}
+
+impl Structure {
+    /// Build the knowledge structure with a backend.
+    pub fn know_struct<K>(&self) -> K
+    where
+        K: KnowStruct<Agent = Agent, Prop = Prop>,
+    {
+        K::new(self.vocab.clone(), self.law.clone(), self.obs.clone())
+    }
+}
+
+impl Display for Structure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "vocab {:?}, law {}, obs", self.vocab, self.law)?;
+        for (ag, props) in &self.obs {
+            write!(f, " {ag}: {props:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A backend under test, deciding the validity of formulas on structures.
+pub struct Checker {
+    name: String,
+    valid: Box<Valid>,
+}
+
+type Valid = dyn Fn(&Structure, Form<Agent, Prop>) -> Result<Option<bool>, String>;
+
+impl Checker {
+    /// Create a checker, which gives `None` for the formulas it cannot check.
+    pub fn new(
+        name: impl Into<String>,
+        valid: impl Fn(&Structure, Form<Agent, Prop>) -> Option<bool> + 'static,
+    ) -> Self {
+        Self::fallible(name, move |st, form| Ok::<_, String>(valid(st, form)))
+    }
+
+    /// Create a checker that may fail, which the harness reports as a [`Failure::Error`].
+    pub fn fallible<E: Display>(
+        name: impl Into<String>,
+        valid: impl Fn(&Structure, Form<Agent, Prop>) -> Result<Option<bool>, E> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            valid: Box::new(move |st, form| valid(st, form).map_err(|err| err.to_string())),
+        }
+    }
+
+    /// Create a checker for a backend that checks every formula.
+    pub fn of<K>(name: impl Into<String>) -> Self
+    where
+        K: KnowStruct<Agent = Agent, Prop = Prop>,
+    {
+        Self::new(name, |st, form| Some(st.know_struct::<K>().valid(form)))
+    }
+
+    /// The checkers of the backends that were compiled in.
+    pub fn enabled() -> Vec<Self> {
+        vec![
+            #[cfg(feature = "bdd")]
+            Self::of::<epistemic_bdd::BddKnowStruct<_, _>>("bdd"),
+            #[cfg(feature = "kripke")]
+            Self::of::<epistemic_kripke::KripkeModel<_, _>>("kripke"),
+            #[cfg(feature = "smcdel")]
+            Self::fallible("smcdel", |st, form| {
+                use epistemic_smcdel::{ProcessError, SmcdelProcess};
+
+                let know: SmcdelProcess<_, _> = st.know_struct();
+                match know.try_check(form) {
+                    Ok(verdict) => Ok(Some(verdict.holds())),
+                    Err(ProcessError::Inexpressible) => Ok(None),
+                    Err(err) => Err(err),
+                }
+            }),
+        ]
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check if `form` is valid on `st`, if this checker can tell.
+    pub fn valid(&self, st: &Structure, form: Form<Agent, Prop>) -> Result<Option<bool>, String> {
+        (self.valid)(st, form)
+    }
+}
+
+impl Debug for Checker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checker")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A formula on which the checkers disagree.
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub structure: Structure,
+    /// The shrunk formula.
+    pub form: Form<Agent, Prop>,
+    /// The validity of the shrunk formula, for each checker that could tell.
+    pub verdicts: Vec<(String, bool)>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "backends disagree on {}", self.form)?;
+        writeln!(f, "  with {}", self.structure)?;
+        for (name, valid) in &self.verdicts {
+            let verdict = if *valid { "valid" } else { "not valid" };
+            writeln!(f, "  {name}: {verdict}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A formula that a checker failed to check.
+#[derive(Debug, Clone)]
+pub struct CheckerError {
+    pub checker: String,
+    pub structure: Structure,
+    pub form: Form<Agent, Prop>,
+    pub message: String,
+}
+
+impl Display for CheckerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} failed on {}: {}",
+            self.checker, self.form, self.message
+        )?;
+        writeln!(f, "  with {}", self.structure)
+    }
+}
+
+/// The reason a run of the harness failed.
+#[derive(Debug, Clone)]
+pub enum Failure {
+    /// The checkers disagree, or one disagrees with the expected verdict of a regression.
+    Disagreement(Disagreement),
+    /// A checker failed.
+    Error(CheckerError),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Disagreement(disagreement) => Display::fmt(disagreement, f),
+            Failure::Error(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+/// Random differential testing of checkers.
+#[derive(Debug)]
+pub struct Harness {
+    gen: Gen,
+    checkers: Vec<Checker>,
+}
+
+impl Harness {
+    /// Create a harness of the enabled backends.
+    pub fn new(gen: Gen) -> Self {
+        Self {
+            gen,
+            checkers: Checker::enabled(),
+        }
+    }
+
+    /// Also run `checker`.
+    pub fn with_checker(mut self, checker: Checker) -> Self {
+        self.checkers.push(checker);
+        self
+    }
+
+    /// The validity of `form` on `st`, for each checker that can tell.
+    pub fn verdicts(
+        &self,
+        st: &Structure,
+        form: &Form<Agent, Prop>,
+    ) -> Result<Vec<(String, bool)>, Box<Failure>> {
+        let mut verdicts = Vec::new();
+        for checker in &self.checkers {
+            match checker.valid(st, form.clone()) {
+                Ok(Some(valid)) => verdicts.push((checker.name.clone(), valid)),
+                Ok(None) => {}
+                Err(message) => {
+                    return Err(Box::new(Failure::Error(CheckerError {
+                        checker: checker.name.clone(),
+                        structure: st.clone(),
+                        form: form.clone(),
+                        message,
+                    })))
+                }
+            }
+        }
+        Ok(verdicts)
+    }
+
+    fn disagree(&self, st: &Structure, form: &Form<Agent, Prop>) -> bool {
+        match self.verdicts(st, form) {
+            Ok(verdicts) => verdicts.iter().any(|(_, valid)| *valid != verdicts[0].1),
+            Err(_) => false,
+        }
+    }
+
+    /// Check `cases` random formulas, each on a random structure, stopping at the first
+    /// disagreement or failed checker.
+    pub fn run(&mut self, cases: usize) -> Result<(), Box<Failure>> {
+        for _ in 0..cases {
+            let structure = self.gen.structure();
+            let form = self.gen.form();
+            self.verdicts(&structure, &form)?;
+            if self.disagree(&structure, &form) {
+                let form = shrink(form, |form| self.disagree(&structure, form));
+                let verdicts = self.verdicts(&structure, &form)?;
+                return Err(Box::new(Failure::Disagreement(Disagreement {
+                    structure,
+                    form,
+                    verdicts,
+                })));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every checker that can tell gives the expected verdict of each regression.
+    pub fn regress(&self, cases: &[Regression]) -> Result<(), Box<Failure>> {
+        for case in cases {
+            let verdicts = self.verdicts(&case.structure, &case.form)?;
+            if verdicts.iter().any(|(_, valid)| *valid != case.valid) {
+                let mut verdicts = verdicts;
+                verdicts.insert(0, ("expected".to_string(), case.valid));
+                return Err(Box::new(Failure::Disagreement(Disagreement {
+                    structure: case.structure.clone(),
+                    form: case.form.clone(),
+                    verdicts,
+                })));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "bdd", feature = "kripke"))]
+mod test {
+    use epistemic::{fold_form, Fold};
+    use epistemic_bdd::BddKnowStruct;
+
+    use super::*;
+
+    #[test]
+    fn test_backends_agree() {
+        if let Err(failure) = Harness::new(Gen::new(0)).run(500) {
+            panic!("{failure}");
+        }
+    }
+
+    #[test]
+    fn test_regressions() {
+        if let Err(failure) = Harness::new(Gen::new(0)).regress(&regressions()) {
+            panic!("{failure}");
+        }
+    }
+
+    /// Mistranslate disjunctions as conjunctions.
+    struct DisjAsConj;
+
+    impl Fold<Agent, Prop> for DisjAsConj {
+        type Agent = Agent;
+        type Prop = Prop;
+        type Error = ();
+
+        fn fold_form(&mut self, form: Form<Agent, Prop>) -> Result<Form<Agent, Prop>, ()> {
+            match fold_form(self, form)? {
+                Form::Disj(ps) => Ok(Form::Conj(ps)),
+                form => Ok(form),
+            }
+        }
+
+        fn fold_agent(&mut self, ag: Agent) -> Result<Agent, ()> {
+            Ok(ag)
+        }
+
+        fn fold_prop(&mut self, p: Prop) -> Result<Prop, ()> {
+            Ok(p)
+        }
+    }
+
+    #[test]
+    fn test_shrink() {
+        let buggy = Checker::new("buggy", |st, form| {
+            let know: BddKnowStruct<_, _> = st.know_struct();
+            Some(know.valid(DisjAsConj.fold_form(form).unwrap()))
+        });
+        let failure = Harness::new(Gen::new(0))
+            .with_checker(buggy)
+            .run(500)
+            .unwrap_err();
+        let Failure::Disagreement(disagreement) = *failure else {
+            panic!("{failure}");
+        };
+
+        // a disjunction of a valid and an invalid formula, of constants where possible.
+        let Form::Disj(ps) = &disagreement.form else {
+            panic!("{disagreement}");
+        };
+        assert!(ps.iter().all(|p| shrinks(p).len() <= 2), "{disagreement}");
+    }
+}
@@ -0,0 +1,118 @@
+use epistemic::Form;
+
+/// Shrink `form` while it `fails`, until none of its [`shrinks`] does.
+pub fn shrink<A, P>(mut form: Form<A, P>, mut fails: impl FnMut(&Form<A, P>) -> bool) -> Form<A, P>
+where
+    A: Clone,
+    P: Clone,
+{
+    'shrink: loop {
+        for smaller in shrinks(&form) {
+            if fails(&smaller) {
+                form = smaller;
+                continue 'shrink;
+            }
+        }
+        return form;
+    }
+}
+
+/// The formulas one step smaller than `form`: the constants, its subformulas, and `form` with an
+/// operand removed or a subformula shrunk.
+pub fn shrinks<A, P>(form: &Form<A, P>) -> Vec<Form<A, P>>
+where
+    A: Clone,
+    P: Clone,
+{
+    let mut smaller = Vec::new();
+    if !matches!(form, Form::Top | Form::Bot) {
+        smaller.extend([Form::Top, Form::Bot]);
+    }
+
+    let children = children(form);
+    smaller.extend(children.iter().map(|&p| p.clone()));
+
+    if let Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) = form {
+        if ps.len() > 2 {
+            for i in 0..ps.len() {
+                let mut ps = ps.clone();
+                ps.remove(i);
+                smaller.push(rebuild(form, ps));
+            }
+        }
+    }
+
+    for (i, child) in children.iter().enumerate() {
+        for p in shrinks(child) {
+            let mut ps: Vec<_> = children.iter().map(|&p| p.clone()).collect();
+            ps[i] = p;
+            smaller.push(rebuild(form, ps));
+        }
+    }
+    smaller
+}
+
+/// The direct subformulas of `form`, except the preconditions of action models.
+fn children<A, P>(form: &Form<A, P>) -> Vec<&Form<A, P>> {
+    match form {
+        Form::Top | Form::Bot | Form::Prop(_) => vec![],
+        Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) => ps.iter().collect(),
+        Form::Neg(p)
+        | Form::Forall(_, p)
+        | Form::Exist(_, p)
+        | Form::K(_, p)
+        | Form::Kw(_, p)
+        | Form::B(_, p)
+        | Form::CK(_, p)
+        | Form::DK(_, p)
+        | Form::CKw(_, p)
+        | Form::DKw(_, p)
+        | Form::Update(_, _, p) => vec![p],
+        Form::Impl(p1, p2)
+        | Form::Equiv(p1, p2)
+        | Form::PA(p1, p2)
+        | Form::PAw(p1, p2)
+        | Form::GA(_, p1, p2)
+        | Form::GAw(_, p1, p2) => vec![p1, p2],
+    }
+}
+
+/// `form` with its [`children`] replaced by `ps`, which may be fewer for connectives of many
+/// operands.
+fn rebuild<A, P>(form: &Form<A, P>, ps: Vec<Form<A, P>>) -> Form<A, P>
+where
+    A: Clone,
+    P: Clone,
+{
+    match form {
+        Form::Conj(_) => return Form::Conj(ps),
+        Form::Disj(_) => return Form::Disj(ps),
+        Form::Xor(_) => return Form::Xor(ps),
+        _ => {}
+    }
+
+    let mut ps = ps.into_iter().map(Box::new);
+    let mut next = || ps.next().expect("a subformula for each child");
+    match form {
+        Form::Top | Form::Bot | Form::Prop(_) | Form::Conj(_) | Form::Disj(_) | Form::Xor(_) => {
+            form.clone()
+        }
+        Form::Neg(_) => Form::Neg(next()),
+        Form::Forall(xs, _) => Form::Forall(xs.clone(), next()),
+        Form::Exist(xs, _) => Form::Exist(xs.clone(), next()),
+        Form::K(ag, _) => Form::K(ag.clone(), next()),
+        Form::Kw(ag, _) => Form::Kw(ag.clone(), next()),
+        Form::B(ag, _) => Form::B(ag.clone(), next()),
+        Form::CK(ags, _) => Form::CK(ags.clone(), next()),
+        Form::DK(ags, _) => Form::DK(ags.clone(), next()),
+        Form::CKw(ags, _) => Form::CKw(ags.clone(), next()),
+        Form::DKw(ags, _) => Form::DKw(ags.clone(), next()),
+        Form::Update(action, events, _) => Form::Update(action.clone(), events.clone(), next()),
+        Form::Impl(..) => Form::Impl(next(), next()),
+        Form::Equiv(..) => Form::Equiv(next(), next()),
+        Form::PA(..) => Form::PA(next(), next()),
+        Form::PAw(..) => Form::PAw(next(), next()),
+        Form::GA(ags, ..) => Form::GA(ags.clone(), next(), next()),
+        Form::GAw(ags, ..) => Form::GAw(ags.clone(), next(), next()),
+    }
+}
@@ -0,0 +1,726 @@
+//! An explicit-state model checker over S5 Kripke models.
+//!
+//! Belief is KD45: each agent may find some worlds plausible, and believes what is true at the
+//! plausible worlds it cannot distinguish from the actual one.
+//!
+//! Every world of the model is enumerated, so this is only suitable for small models. In exchange,
+//! the model can be inspected directly, which makes it useful for debugging and as a reference
+//! for the symbolic backends.
+//!
+//! As in the symbolic backends, a quantified proposition may take a different value at each
+//! world: `Forall(xs, p)` is true at a world when `p` is true at every world that differs from it
+//! at most on `xs`. Knowledge structures are therefore enumerated in full, and their law only
+//! decides which worlds are possible. Quantified propositions that no world mentions are added to
+//! the model, unobserved by every agent.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use epistemic::{ActionModel, Announced, Counterexample, Form, KnowStruct, Semantics, Verdict};
+
+/// A world of a [`KripkeModel`].
+pub type World = usize;
+
+/// A multi-agent S5 Kripke model.
+///
+/// When the model has an actual world, a formula is satisfied when it is true there; otherwise,
+/// it is satisfied when it is true at every possible world.
+#[derive(Debug, Clone)]
+pub struct KripkeModel<A, P> {
+    /// The propositions true at each world.
+    valuations: Vec<BTreeSet<P>>,
+    /// The outcomes of the announcements and events that led to each world, which quantifiers
+    /// keep fixed.
+    history: Vec<Vec<usize>>,
+    /// Whether each world is possible. Impossible worlds are only reached by quantifiers.
+    lawful: Vec<bool>,
+    /// The equivalence class of each world, for each agent.
+    ///
+    /// Agents missing from this map cannot distinguish any worlds.
+    relations: BTreeMap<A, Vec<usize>>,
+    /// Whether each world is plausible, for each agent.
+    ///
+    /// Agents missing from this map believe exactly what they know.
+    plausible: BTreeMap<A, Vec<bool>>,
+    /// The actual world.
+    actual: Option<World>,
+}
+
+impl<A, P> KripkeModel<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    /// Create a model from the valuations of its worlds and the partition of the worlds for each
+    /// agent.
+    ///
+    /// Worlds missing from an agent's partition are distinguishable from every other world.
+    pub fn new(valuations: Vec<BTreeSet<P>>, partitions: BTreeMap<A, Vec<Vec<World>>>) -> Self {
+        let n = valuations.len();
+        let relations = partitions
+            .into_iter()
+            .map(|(ag, blocks)| {
+                // worlds outside of every block are their own class.
+                let mut classes: Vec<_> = (blocks.len()..blocks.len() + n).collect();
+                for (i, block) in blocks.into_iter().enumerate() {
+                    for w in block {
+                        classes[w] = i;
+                    }
+                }
+                (ag, classes)
+            })
+            .collect();
+
+        Self {
+            valuations,
+            history: vec![Vec::new(); n],
+            lawful: vec![true; n],
+            relations,
+            plausible: BTreeMap::new(),
+            actual: None,
+        }
+    }
+
+    /// Let `agent` consider the worlds in `plausible` most likely.
+    pub fn with_belief(mut self, agent: A, plausible: impl IntoIterator<Item = World>) -> Self {
+        let mut truth = vec![false; self.valuations.len()];
+        for w in plausible {
+            truth[w] = true;
+        }
+        self.plausible.insert(agent, truth);
+        self
+    }
+
+    /// Designate `world` as the actual world.
+    pub fn with_actual(self, world: World) -> Self {
+        Self {
+            actual: Some(world),
+            ..self
+        }
+    }
+
+    /// The actual world, if there is one.
+    pub fn actual(&self) -> Option<World> {
+        self.actual
+    }
+
+    /// Iterate over the possible worlds.
+    pub fn worlds(&self) -> impl Iterator<Item = World> + '_ {
+        (0..self.valuations.len()).filter(|&w| self.lawful[w])
+    }
+
+    /// The propositions true at `world`.
+    pub fn valuation(&self, world: World) -> &BTreeSet<P> {
+        &self.valuations[world]
+    }
+
+    /// Iterate over the possible worlds that `ag` cannot distinguish from `world`.
+    pub fn indistinguishable<'a>(
+        &'a self,
+        ag: &'a A,
+        world: World,
+    ) -> impl Iterator<Item = World> + 'a {
+        self.worlds()
+            .filter(move |&v| self.class(ag, v) == self.class(ag, world))
+    }
+
+    /// Compute the possible worlds at which `form` is true.
+    pub fn truth_set(&self, form: &Form<A, P>) -> BTreeSet<World> {
+        let truth = self.truth(form);
+        self.worlds().filter(|&w| truth[w]).collect()
+    }
+
+    /// Compute the truth of `form` at each world.
+    fn truth(&self, form: &Form<A, P>) -> Vec<bool> {
+        let mut truth = self.extend(form.binders()).eval(form);
+        // the worlds of the model come first in its extension.
+        truth.truncate(self.valuations.len());
+        truth
+    }
+
+    fn class(&self, ag: &A, world: World) -> usize {
+        match self.relations.get(ag) {
+            Some(classes) => classes[world],
+            None => 0,
+        }
+    }
+
+    /// Compute the truth of `form` at each world, whose quantified propositions are all mentioned
+    /// by the model.
+    fn eval(&self, form: &Form<A, P>) -> Vec<bool> {
+        let n = self.valuations.len();
+        match form {
+            Form::Top => vec![true; n],
+            Form::Bot => vec![false; n],
+            Form::Prop(p) => self.valuations.iter().map(|val| val.contains(p)).collect(),
+            Form::Neg(p) => negate(&self.eval(p)),
+            Form::Conj(ps) => ps.iter().fold(vec![true; n], |acc, p| {
+                zip_with(&acc, &self.eval(p), |b1, b2| b1 && b2)
+            }),
+            Form::Disj(ps) => ps.iter().fold(vec![false; n], |acc, p| {
+                zip_with(&acc, &self.eval(p), |b1, b2| b1 || b2)
+            }),
+            Form::Xor(ps) => ps.iter().fold(vec![false; n], |acc, p| {
+                zip_with(&acc, &self.eval(p), |b1, b2| b1 != b2)
+            }),
+            Form::Impl(p1, p2) => zip_with(&self.eval(p1), &self.eval(p2), |b1, b2| !b1 || b2),
+            Form::Equiv(p1, p2) => zip_with(&self.eval(p1), &self.eval(p2), |b1, b2| b1 == b2),
+            Form::Forall(xs, p) => self.forall(xs, &self.eval(p)),
+            Form::Exist(xs, p) => negate(&self.forall(xs, &negate(&self.eval(p)))),
+            Form::K(ag, p) => self.know([ag], &self.eval(p)),
+            Form::Kw(ag, p) => {
+                let truth = self.eval(p);
+                let yes = self.know([ag], &truth);
+                let no = self.know([ag], &negate(&truth));
+                zip_with(&yes, &no, |b1, b2| b1 || b2)
+            }
+            Form::B(ag, p) => self.believe(ag, &self.eval(p)),
+            Form::CK(ags, p) => self.common(ags, &self.eval(p)),
+            Form::DK(ags, p) => self.know(ags, &self.eval(p)),
+            Form::CKw(ags, p) => {
+                let truth = self.eval(p);
+                let yes = self.common(ags, &truth);
+                let no = self.common(ags, &negate(&truth));
+                zip_with(&yes, &no, |b1, b2| b1 || b2)
+            }
+            Form::DKw(ags, p) => {
+                let truth = self.eval(p);
+                let yes = self.know(ags, &truth);
+                let no = self.know(ags, &negate(&truth));
+                zip_with(&yes, &no, |b1, b2| b1 || b2)
+            }
+            Form::PA(p1, p2) => {
+                let pre = self.eval(p1);
+                let after = self.restrict(&pre).eval(p2);
+                zip_with(&pre, &after, |b1, b2| !b1 || b2)
+            }
+            Form::PAw(p1, p2) => {
+                let pre = self.eval(p1);
+                let yes = self.restrict(&pre).eval(p2);
+                let no = self.restrict(&negate(&pre)).eval(p2);
+                (0..n)
+                    .map(|w| if pre[w] { yes[w] } else { no[w] })
+                    .collect()
+            }
+            Form::GA(ags, p1, p2) => {
+                let pre = self.eval(p1);
+                let after = self.refine(ags, &pre).eval(p2);
+                (0..n).map(|w| !pre[w] || after[n + w]).collect()
+            }
+            Form::GAw(ags, p1, p2) => {
+                let pre = self.eval(p1);
+                let after = self.refine(ags, &pre).eval(p2);
+                (0..n)
+                    .map(|w| if pre[w] { after[n + w] } else { after[w] })
+                    .collect()
+            }
+            Form::Update(action, events, p) => {
+                let pre: Vec<_> = action.events().map(|e| self.eval(action.pre(e))).collect();
+                let after = self.update(action, &pre).eval(p);
+                (0..n)
+                    .map(|w| events.iter().all(|&e| !pre[e][w] || after[e * n + w]))
+                    .collect()
+            }
+        }
+    }
+
+    /// Follow the announcements of `form` from `world`, at which it is false, to the point at
+    /// which it is falsified, and report the values of `props` there.
+    fn counterexample(
+        &self,
+        world: World,
+        form: &Form<A, P>,
+        props: BTreeSet<P>,
+    ) -> Counterexample<A, P> {
+        let (mut model, mut world, mut form) = (self.clone(), world, form);
+        let mut announcements = Vec::new();
+        loop {
+            let (group, pre, post) = match form {
+                Form::PA(p1, p2) | Form::PAw(p1, p2) => (None, p1, p2),
+                Form::GA(ags, p1, p2) | Form::GAw(ags, p1, p2) => (Some(ags), p1, p2),
+                _ => break,
+            };
+
+            let pre_truth = model.eval(pre);
+            let truth = pre_truth[world];
+            model = match group {
+                None => {
+                    let keep: Vec<_> = pre_truth.iter().map(|&b| b == truth).collect();
+                    model.restrict(&keep)
+                }
+                Some(ags) => {
+                    if truth {
+                        world += model.valuations.len();
+                    }
+                    model.refine(ags, &pre_truth)
+                }
+            };
+
+            announcements.push(Announced {
+                group: group.cloned(),
+                form: (**pre).clone(),
+                truth,
+            });
+            form = post;
+        }
+
+        let val = model.valuation(world);
+        let world = props
+            .into_iter()
+            .map(|p| {
+                let b = val.contains(&p);
+                (p, b)
+            })
+            .collect();
+        Counterexample {
+            world,
+            announcements,
+        }
+    }
+
+    /// The truth of `Forall(xs, _)`, given the truth of its body: it holds at a world when the
+    /// body holds at every world that differs from it at most on `xs`.
+    fn forall(&self, xs: &[P], truth: &[bool]) -> Vec<bool> {
+        let n = self.valuations.len();
+        let key = |w: World| {
+            let val: BTreeSet<_> = self.valuations[w]
+                .iter()
+                .filter(|p| !xs.contains(p))
+                .collect();
+            (val, &self.history[w])
+        };
+
+        let mut all = BTreeMap::new();
+        for (w, &b) in truth.iter().enumerate() {
+            *all.entry(key(w)).or_insert(true) &= b;
+        }
+
+        (0..n).map(|w| all[&key(w)]).collect()
+    }
+
+    /// The (distributed) knowledge of `truth` amongst `ags`.
+    fn know<'a>(&self, ags: impl IntoIterator<Item = &'a A>, truth: &[bool]) -> Vec<bool>
+    where
+        A: 'a,
+    {
+        let ags: Vec<_> = ags.into_iter().collect();
+        let key = |w| -> Vec<_> { ags.iter().map(|ag| self.class(ag, w)).collect() };
+
+        // a class is known to have `truth` if it holds at every possible world of it.
+        let mut known = BTreeMap::new();
+        for w in self.worlds() {
+            *known.entry(key(w)).or_insert(true) &= truth[w];
+        }
+
+        (0..self.valuations.len())
+            .map(|w| known.get(&key(w)).copied().unwrap_or(true))
+            .collect()
+    }
+
+    /// The belief of `truth` by `ag`: it holds at the plausible worlds `ag` considers possible,
+    /// or at all of them if none are plausible.
+    fn believe(&self, ag: &A, truth: &[bool]) -> Vec<bool> {
+        let known = self.know([ag], truth);
+        let Some(plausible) = self.plausible.get(ag) else {
+            return known;
+        };
+
+        // for each class, whether any world is plausible and whether `truth` holds at all of them.
+        let mut believed = BTreeMap::new();
+        for w in self.worlds().filter(|&w| plausible[w]) {
+            *believed.entry(self.class(ag, w)).or_insert(true) &= truth[w];
+        }
+
+        (0..self.valuations.len())
+            .map(|w| match believed.get(&self.class(ag, w)) {
+                Some(&b) => b,
+                None => known[w],
+            })
+            .collect()
+    }
+
+    /// The common knowledge of `truth` amongst `ags`, as a greatest fixpoint.
+    fn common(&self, ags: &[A], truth: &[bool]) -> Vec<bool> {
+        let mut z = truth.to_vec();
+        loop {
+            let next = ags.iter().fold(z.clone(), |acc, ag| {
+                zip_with(&acc, &self.know([ag], &z), |b1, b2| b1 && b2)
+            });
+            if next == z {
+                return z;
+            }
+            z = next;
+        }
+    }
+
+    /// The model in which only the possible worlds in `keep` remain possible.
+    fn restrict(&self, keep: &[bool]) -> Self {
+        Self {
+            lawful: zip_with(&self.lawful, keep, |b1, b2| b1 && b2),
+            actual: None,
+            ..self.clone()
+        }
+    }
+
+    /// The model with `copies` copies of each world, in which each copy keeps the valuation,
+    /// classes and plausibility of its world, and records its number in its history.
+    ///
+    /// The copies of each number come together, in the order of the worlds.
+    fn copy(&self, copies: usize) -> Self {
+        let n = self.valuations.len();
+        let each = |w: World| w % n;
+        let worlds = 0..copies * n;
+
+        Self {
+            valuations: worlds
+                .clone()
+                .map(|w| self.valuations[each(w)].clone())
+                .collect(),
+            history: worlds
+                .clone()
+                .map(|w| {
+                    let mut history = self.history[each(w)].clone();
+                    history.push(w / n);
+                    history
+                })
+                .collect(),
+            lawful: worlds.clone().map(|w| self.lawful[each(w)]).collect(),
+            relations: self
+                .relations
+                .iter()
+                .map(|(ag, classes)| {
+                    (
+                        ag.clone(),
+                        worlds.clone().map(|w| classes[each(w)]).collect(),
+                    )
+                })
+                .collect(),
+            plausible: self
+                .plausible
+                .iter()
+                .map(|(ag, truth)| (ag.clone(), worlds.clone().map(|w| truth[each(w)]).collect()))
+                .collect(),
+            actual: None,
+        }
+    }
+
+    /// The model in which each of `props` that no world mentions may take either value, unseen by
+    /// every agent. The worlds of this model come first, with `props` false.
+    fn extend<'a>(&self, props: impl IntoIterator<Item = &'a P>) -> Self
+    where
+        P: 'a,
+    {
+        let mentioned: BTreeSet<_> = self.valuations.iter().flatten().collect();
+        let fresh: BTreeSet<_> = props
+            .into_iter()
+            .filter(|p| !mentioned.contains(p))
+            .collect();
+
+        fresh.into_iter().fold(self.clone(), |model, p| {
+            let n = model.valuations.len();
+            let mut extended = model.copy(2);
+            for w in n..2 * n {
+                extended.valuations[w].insert(p.clone());
+            }
+            // the proposition is not the outcome of an announcement.
+            extended.history = model
+                .history
+                .iter()
+                .chain(&model.history)
+                .cloned()
+                .collect();
+            extended.actual = model.actual;
+            extended
+        })
+    }
+
+    /// The model in which each of `ags` additionally distinguishes worlds by `truth`.
+    ///
+    /// Each world is copied for each outcome of the announcement: first the copies at which
+    /// `truth` was announced false, then those at which it was announced true. A copy is possible
+    /// when its world is and the announcement is true.
+    fn refine(&self, ags: &[A], truth: &[bool]) -> Self {
+        let n = self.valuations.len();
+        let mut model = self.copy(2);
+        for w in 0..2 * n {
+            model.lawful[w] &= (w >= n) == truth[w % n];
+        }
+        for ag in ags {
+            let classes = (0..2 * n)
+                .map(|w| 2 * self.class(ag, w % n) + w / n)
+                .collect();
+            model.relations.insert(ag.clone(), classes);
+        }
+        model
+    }
+
+    /// The product of this model with `action`, given the truth of the precondition of each
+    /// event at each world.
+    ///
+    /// Each world is copied for each event, in the order of the events. A copy is possible when
+    /// its world is and the precondition of its event holds there.
+    fn update(&self, action: &ActionModel<A, P>, pre: &[Vec<bool>]) -> Self {
+        let n = self.valuations.len();
+        let events = action.events().len();
+        let mut product = self.copy(events);
+        for w in 0..events * n {
+            product.lawful[w] &= pre[w / n][w % n];
+        }
+
+        // two copies are indistinguishable when both their worlds and their events are.
+        let mut agents: BTreeSet<_> = self.relations.keys().collect();
+        agents.extend(action.agents());
+        for ag in agents {
+            let mut ids = BTreeMap::new();
+            let classes = (0..events * n)
+                .map(|w| {
+                    let next = ids.len();
+                    *ids.entry((self.class(ag, w % n), action.class(ag, w / n)))
+                        .or_insert(next)
+                })
+                .collect();
+            product.relations.insert(ag.clone(), classes);
+        }
+        product
+    }
+}
+
+fn zip_with(xs: &[bool], ys: &[bool], f: impl Fn(bool, bool) -> bool) -> Vec<bool> {
+    xs.iter().zip(ys).map(|(&x, &y)| f(x, y)).collect()
+}
+
+fn negate(xs: &[bool]) -> Vec<bool> {
+    xs.iter().map(|&x| !x).collect()
+}
+
+impl<A, P> Semantics for KripkeModel<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    type Agent = A;
+    type Prop = P;
+
+    fn check(&self, form: Form<Self::Agent, Self::Prop>) -> Verdict<Self::Agent, Self::Prop> {
+        let props = self
+            .valuations
+            .iter()
+            .flatten()
+            .chain(form.vocab())
+            .cloned()
+            .collect();
+
+        // the worlds of the model come first in its extension.
+        let model = self.extend(form.binders());
+        let truth = model.eval(&form);
+        let falsified = match self.actual {
+            Some(w) => (!truth[w]).then_some(w),
+            None => self.worlds().find(|&w| !truth[w]),
+        };
+
+        match falsified {
+            None => Verdict::Holds,
+            Some(w) => Verdict::Fails(model.counterexample(w, &form, props)),
+        }
+    }
+}
+
+impl<A, P> KnowStruct for KripkeModel<A, P>
+where
+    A: Ord + Clone,
+    P: Ord + Clone,
+{
+    /// Enumerate the states of the knowledge structure as worlds.
+    ///
+    /// Each valuation of `vocab` is a world, which is possible when it satisfies `law`, and an
+    /// agent cannot distinguish two worlds when they agree on the propositions it observes. As in
+    /// `epistemic-bdd`, the law is interpreted over the trivial model, in which no agent observes
+    /// anything.
+    fn new(
+        vocab: Vec<Self::Prop>,
+        law: Form<Self::Agent, Self::Prop>,
+        obs: Vec<(Self::Agent, Vec<Self::Prop>)>,
+    ) -> Self {
+        let vocab: BTreeSet<_> = vocab.into_iter().collect();
+        let valuations = vocab.iter().fold(vec![BTreeSet::new()], |vals, p| {
+            vals.into_iter()
+                .flat_map(|val| {
+                    let mut with = val.clone();
+                    with.insert(p.clone());
+                    [val, with]
+                })
+                .collect()
+        });
+        let lawful = Self::new(valuations.clone(), BTreeMap::new()).truth(&law);
+
+        let obs = obs.into_iter().fold(
+            BTreeMap::new(),
+            |mut acc: BTreeMap<_, BTreeSet<_>>, (ag, props)| {
+                acc.entry(ag).or_default().extend(props);
+                acc
+            },
+        );
+        let partitions = obs
+            .iter()
+            .map(|(ag, props)| {
+                let mut blocks: BTreeMap<Vec<_>, Vec<_>> = BTreeMap::new();
+                for (w, val) in valuations.iter().enumerate() {
+                    let seen = val.intersection(props).collect();
+                    blocks.entry(seen).or_default().push(w);
+                }
+                (ag.clone(), blocks.into_values().collect())
+            })
+            .collect();
+
+        Self {
+            lawful,
+            ..Self::new(valuations, partitions)
+        }
+    }
+
+    /// Check if `form` is true at every possible world, regardless of the actual world.
+    fn valid(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
+        let truth = self.truth(&form);
+        self.worlds().all(|w| truth[w])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use epistemic::{ActionModel, Form, KnowStruct, Semantics};
+
+    use super::KripkeModel;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn p(x: &'static str) -> F {
+        Form::Prop(x)
+    }
+
+    fn neg(p: F) -> F {
+        Form::Neg(Box::new(p))
+    }
+
+    fn know(ag: &'static str, p: F) -> F {
+        Form::K(ag, Box::new(p))
+    }
+
+    #[test]
+    fn test_explicit() {
+        // `a` cannot tell the two worlds apart; `b` can.
+        let valuations = vec![BTreeSet::from(["x"]), BTreeSet::new()];
+        let partitions = BTreeMap::from([("a", vec![vec![0, 1]]), ("b", vec![vec![0], vec![1]])]);
+        let model = KripkeModel::new(valuations, partitions).with_actual(0);
+
+        assert!(model.sat(p("x")));
+        assert!(model.sat(know("b", p("x"))));
+        assert!(model.sat(neg(know("a", p("x")))));
+        assert!(model.sat(know("a", Form::DKw(vec!["b"], Box::new(p("x"))))));
+        assert!(model.sat(Form::Kw("b", Box::new(p("x")))));
+        assert!(!model.sat(Form::Kw("a", Box::new(p("x")))));
+        assert_eq!(model.truth_set(&know("b", p("x"))), BTreeSet::from([0]));
+        assert_eq!(
+            model.indistinguishable(&"a", 0).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        assert!(!model.valid(p("x")));
+        assert!(model.satisfiable(neg(p("x"))));
+        assert!(model.equivalent(know("b", p("x")), p("x")));
+        assert!(model.entails(know("a", p("x")), Form::Bot));
+    }
+
+    #[test]
+    fn test_belief() {
+        // `a` wrongly finds only the world without `x` plausible.
+        let valuations = vec![BTreeSet::from(["x"]), BTreeSet::new()];
+        let partitions = BTreeMap::from([("a", vec![vec![0, 1]])]);
+        let model = KripkeModel::new(valuations, partitions)
+            .with_belief("a", [1])
+            .with_actual(0);
+
+        let believe = |ag, p| Form::B(ag, Box::new(p));
+        assert!(model.sat(believe("a", neg(p("x")))));
+        assert!(!model.sat(know("a", neg(p("x")))));
+        assert!(!model.sat(believe("a", Form::Bot)));
+        assert!(!model.sat(believe("b", p("x"))));
+        assert!(model.sat(Form::PA(Box::new(p("x")), Box::new(believe("a", p("x"))))));
+    }
+
+    #[test]
+    fn test_update() {
+        let valuations = vec![BTreeSet::from(["x"]), BTreeSet::new()];
+        let model = KripkeModel::new(valuations, BTreeMap::<_, Vec<Vec<_>>>::new()).with_actual(0);
+
+        // `b` learns whether `x`, and `a` suspects that it might have.
+        let action = ActionModel::new(vec![p("x"), neg(p("x")), Form::Top])
+            .with_partition("b", vec![vec![0], vec![1], vec![2]]);
+        let update = |p| Form::Update(Box::new(action.clone()), vec![0, 1], Box::new(p));
+
+        assert!(model.sat(update(know("b", p("x")))));
+        assert!(model.sat(update(neg(know("a", Form::Kw("b", Box::new(p("x"))))))));
+        assert!(model.sat(update(Form::CK(vec!["a"], Box::new(Form::Top)))));
+        assert!(model.sat(neg(know("b", p("x")))));
+    }
+
+    #[test]
+    fn test_know_struct() {
+        let model: KripkeModel<_, _> = KnowStruct::new(
+            vec!["x", "y"],
+            Form::Impl(Box::new(p("x")), Box::new(p("y"))),
+            vec![("a", vec!["x"]), ("b", vec!["y"])],
+        );
+
+        assert_eq!(model.worlds().count(), 3);
+        assert!(model.sat(Form::Impl(Box::new(p("x")), Box::new(know("a", p("y"))))));
+        assert!(!model.sat(know("b", p("x"))));
+        assert!(model.sat(Form::GA(
+            vec!["b"],
+            Box::new(p("x")),
+            Box::new(Form::CK(vec!["a", "b"], Box::new(p("y"))))
+        )));
+        assert!(model.sat(Form::PAw(
+            Box::new(p("y")),
+            Box::new(Form::CKw(vec!["a", "b"], Box::new(p("y"))))
+        )));
+        assert!(model.sat(Form::Forall(
+            vec!["x"],
+            Box::new(Form::Disj(vec![p("x"), neg(p("x"))]))
+        )));
+
+        // `a` knows nothing in the trivial model, so the law rules out no world.
+        let model: KripkeModel<_, _> =
+            KnowStruct::new(vec!["x"], neg(know("a", p("x"))), vec![("a", vec!["x"])]);
+        assert_eq!(model.worlds().count(), 2);
+        assert!(!model.valid(neg(know("a", p("x")))));
+    }
+
+    #[test]
+    fn test_quantifiers() {
+        let model: KripkeModel<&str, &str> =
+            KnowStruct::new(vec!["x", "y"], Form::Top, vec![("a", vec!["x"])]);
+
+        assert!(model.valid(Form::Exist(vec!["z"], Box::new(p("z")))));
+        assert!(model.valid(know(
+            "a",
+            Form::Exist(
+                vec!["y"],
+                Box::new(Form::Equiv(Box::new(p("x")), Box::new(p("y"))))
+            )
+        )));
+        assert!(!model.valid(Form::Forall(
+            vec!["y"],
+            Box::new(Form::Disj(vec![p("x"), p("y")]))
+        )));
+
+        // the chosen value of a quantified proposition is only known to the agents that see it.
+        assert!(!model.valid(Form::Exist(vec!["y"], Box::new(know("a", p("y"))))));
+        assert!(model.valid(Form::Exist(vec!["x"], Box::new(know("a", p("x"))))));
+        assert!(model.valid(Form::GA(
+            vec!["a"],
+            Box::new(p("y")),
+            Box::new(Form::Forall(vec!["z"], Box::new(know("a", p("y")))))
+        )));
+    }
+}
@@ -0,0 +1,193 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use epistemic::{Counterexample, Form, KnowStruct, Semantics, Verdict};
+use smcdel::schema::{self, Request};
+use smcdel::{Haskell, HaskellError};
+
+use crate::text::{CheckInput, Job};
+
+/// A knowledge structure checked by SMCDEL, through the Haskell bridge.
+///
+/// The Haskell runtime is initialized on the first check, unless [`Haskell::init`] was called
+/// before.
+#[derive(Debug, Clone)]
+pub struct SMCDEL<A, P> {
+    vocab: Vec<P>,
+    law: Form<A, P>,
+    obs: Vec<(A, Vec<P>)>,
+}
+
+/// An error checking a formula through the Haskell bridge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FfiError {
+    /// SMCDEL cannot express the formula, even without announcements.
+    Inexpressible,
+    /// The Haskell runtime failed.
+    Haskell(HaskellError),
+}
+
+impl Display for FfiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FfiError::Inexpressible => write!(f, "SMCDEL cannot express the formula"),
+            FfiError::Haskell(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for FfiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FfiError::Inexpressible => None,
+            FfiError::Haskell(err) => Some(err),
+        }
+    }
+}
+
+impl From<HaskellError> for FfiError {
+    fn from(err: HaskellError) -> Self {
+        FfiError::Haskell(err)
+    }
+}
+
+impl<A, P> SMCDEL<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + PartialEq,
+{
+    /// Number the propositions and agents of the structure and `form`, and translate them into
+    /// the bridge's schema. Also return the proposition of each number.
+    fn request(&self, form: Form<A, P>) -> Result<(Request, Vec<P>), FfiError> {
+        let mut input = CheckInput::new(self.vocab.clone(), self.law.clone(), self.obs.clone());
+        input.jobs.push(Job::Valid(form));
+        let (input, props) = input.numbered();
+
+        let mut agents = Vec::new();
+        let mut name = |ag: A| match agents.iter().position(|other| *other == ag) {
+            Some(i) => i.to_string(),
+            None => {
+                agents.push(ag);
+                (agents.len() - 1).to_string()
+            }
+        };
+
+        let obs = input
+            .obs
+            .into_iter()
+            .map(|(ag, ps)| (name(ag), ps))
+            .collect();
+        let law = translate(input.law.map_agents(&mut name))?;
+        let form = match input.jobs.into_iter().next() {
+            Some(Job::Valid(form)) => translate(form.map_agents(&mut name))?,
+            _ => unreachable!("the input has a single `VALID?` job"),
+        };
+
+        let structure = schema::KnowStruct {
+            vocab: input.vocab,
+            law,
+            obs,
+        };
+        Ok((Request { structure, form }, props))
+    }
+
+    /// Check `form` with SMCDEL, producing a counterexample if it does not hold.
+    pub fn try_check(&self, form: Form<A, P>) -> Result<Verdict<A, P>, FfiError>
+    where
+        P: Ord,
+    {
+        let (request, props) = self.request(form)?;
+        match Haskell::get()?.check(&request)? {
+            schema::Verdict::Holds => Ok(Verdict::Holds),
+            schema::Verdict::Fails(state) => {
+                // numbers start from `1`, in order of first occurrence.
+                let world = self.vocab.iter().map(|p| {
+                    let n = props.iter().position(|q| q == p).unwrap() as u32 + 1;
+                    (p.clone(), state.contains(&n))
+                });
+                Ok(Verdict::Fails(Counterexample::new(world.collect())))
+            }
+            schema::Verdict::Error(err) => Err(HaskellError::Check(err).into()),
+        }
+    }
+}
+
+/// Translate `form` into the bridge's schema, first reducing action models into announcements
+/// if SMCDEL cannot express them.
+fn translate(form: Form<String, u32>) -> Result<schema::Form, FfiError> {
+    if let Some(form) = to_schema(&form) {
+        return Ok(form);
+    }
+    let form = form
+        .reduce_announcements()
+        .map_err(|_| FfiError::Inexpressible)?;
+    to_schema(&form).ok_or(FfiError::Inexpressible)
+}
+
+/// Translate `form` into the bridge's schema, if SMCDEL can express it.
+fn to_schema(form: &Form<String, u32>) -> Option<schema::Form> {
+    let one = |p: &Form<String, u32>| to_schema(p).map(Box::new);
+    let all = |ps: &[Form<String, u32>]| ps.iter().map(to_schema).collect::<Option<Vec<_>>>();
+
+    let form = match form {
+        Form::Top => schema::Form::Top,
+        Form::Bot => schema::Form::Bot,
+        Form::Prop(p) => schema::Form::PrpF(*p),
+        Form::Neg(p) => schema::Form::Neg(one(p)?),
+        Form::Conj(ps) => schema::Form::Conj(all(ps)?),
+        Form::Disj(ps) => schema::Form::Disj(all(ps)?),
+        Form::Xor(ps) => schema::Form::Xor(all(ps)?),
+        Form::Impl(p1, p2) => schema::Form::Impl(one(p1)?, one(p2)?),
+        Form::Equiv(p1, p2) => schema::Form::Equi(one(p1)?, one(p2)?),
+        Form::Forall(ps, p) => schema::Form::Forall(ps.clone(), one(p)?),
+        Form::Exist(ps, p) => schema::Form::Exists(ps.clone(), one(p)?),
+        Form::K(ag, p) => schema::Form::K(ag.clone(), one(p)?),
+        Form::Kw(ag, p) => schema::Form::Kw(ag.clone(), one(p)?),
+        Form::CK(ags, p) => schema::Form::Ck(ags.clone(), one(p)?),
+        Form::DK(ags, p) => schema::Form::Dk(ags.clone(), one(p)?),
+        Form::CKw(ags, p) => schema::Form::Ckw(ags.clone(), one(p)?),
+        Form::DKw(ags, p) => schema::Form::Dkw(ags.clone(), one(p)?),
+        Form::PA(p1, p2) => schema::Form::PubAnnounce(one(p1)?, one(p2)?),
+        Form::PAw(p1, p2) => schema::Form::PubAnnounceW(one(p1)?, one(p2)?),
+        Form::GA(ags, p1, p2) => schema::Form::Announce(ags.clone(), one(p1)?, one(p2)?),
+        Form::GAw(ags, p1, p2) => schema::Form::AnnounceW(ags.clone(), one(p1)?, one(p2)?),
+        Form::B(..) | Form::Update(..) => return None,
+    };
+    Some(form)
+}
+
+impl<A, P> Semantics for SMCDEL<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + Ord,
+{
+    type Agent = A;
+    type Prop = P;
+
+    /// Check `form` with SMCDEL.
+    ///
+    /// # Panics
+    ///
+    /// Panics if SMCDEL cannot express `form`, or fails to check it.
+    fn check(&self, form: Form<Self::Agent, Self::Prop>) -> Verdict<Self::Agent, Self::Prop> {
+        self.try_check(form).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<A, P> KnowStruct for SMCDEL<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + Ord,
+{
+    fn new(
+        vocab: Vec<Self::Prop>,
+        law: Form<Self::Agent, Self::Prop>,
+        obs: Vec<(Self::Agent, Vec<Self::Prop>)>,
+    ) -> Self {
+        Self { vocab, law, obs }
+    }
+
+    fn valid(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
+        self.sat(form)
+    }
+}
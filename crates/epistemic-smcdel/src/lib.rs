@@ -1 +1,10 @@
-pub struct SMCDEL;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "process")]
+mod process;
+pub mod text;
+
+#[cfg(feature = "ffi")]
+pub use ffi::{FfiError, SMCDEL};
+#[cfg(feature = "process")]
+pub use process::{ProcessError, SmcdelProcess};
@@ -0,0 +1,355 @@
+use std::env;
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use epistemic::{Counterexample, Form, KnowStruct, Semantics, Verdict};
+use wait_timeout::ChildExt;
+
+use crate::text::{CheckInput, Job};
+
+/// A knowledge structure checked by running SMCDEL's standalone `smcdel` executable on an input
+/// file in its text format.
+///
+/// The executable is `$SMCDEL` if set, or else `smcdel` on the `PATH`.
+#[derive(Debug, Clone)]
+pub struct SmcdelProcess<A, P> {
+    vocab: Vec<P>,
+    law: Form<A, P>,
+    obs: Vec<(A, Vec<P>)>,
+    executable: PathBuf,
+    timeout: Duration,
+}
+
+/// An error running the checker executable.
+#[derive(Debug)]
+pub enum ProcessError {
+    /// The executable does not exist.
+    NotFound(PathBuf),
+    /// The executable did not finish within the timeout.
+    Timeout(Duration),
+    /// The executable exited unsuccessfully, with its standard error.
+    Failed(ExitStatus, String),
+    /// The executable printed no answer to the question, with its standard output.
+    Output(String),
+    /// SMCDEL's text format cannot express the formula, even without announcements.
+    Inexpressible,
+    /// Writing the input or reading the output failed.
+    Io(io::Error),
+}
+
+impl Display for ProcessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::NotFound(path) => {
+                write!(f, "SMCDEL executable `{}` not found", path.display())
+            }
+            ProcessError::Timeout(timeout) => {
+                write!(f, "SMCDEL did not finish within {}s", timeout.as_secs_f64())
+            }
+            ProcessError::Failed(status, stderr) => write!(f, "SMCDEL failed ({status}): {stderr}"),
+            ProcessError::Output(stdout) => write!(f, "unexpected output from SMCDEL: {stdout}"),
+            ProcessError::Inexpressible => write!(f, "SMCDEL cannot express the formula"),
+            ProcessError::Io(err) => write!(f, "failed to run SMCDEL: {err}"),
+        }
+    }
+}
+
+impl Error for ProcessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProcessError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProcessError {
+    fn from(err: io::Error) -> Self {
+        ProcessError::Io(err)
+    }
+}
+
+impl<A, P> SmcdelProcess<A, P> {
+    /// Run `executable` instead of the default.
+    pub fn with_executable(mut self, executable: impl Into<PathBuf>) -> Self {
+        self.executable = executable.into();
+        self
+    }
+
+    /// Give up on a check after `timeout`, by default a minute.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run the executable on `input`, returning its standard output.
+    fn run(&self, input: &str) -> Result<String, ProcessError> {
+        let mut file = tempfile::Builder::new().suffix(".smcdel.txt").tempfile()?;
+        file.write_all(input.as_bytes())?;
+        file.flush()?;
+
+        let mut child = Command::new(&self.executable)
+            .arg(file.path())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => ProcessError::NotFound(self.executable.clone()),
+                _ => ProcessError::Io(err),
+            })?;
+
+        // read the pipes concurrently, so that the child never blocks on a full pipe.
+        let read = |pipe: Option<Box<dyn Read + Send>>| {
+            thread::spawn(move || {
+                let mut out = String::new();
+                pipe.map_or(Ok(0), |mut pipe| pipe.read_to_string(&mut out))
+                    .map(|_| out)
+            })
+        };
+        let stdout = read(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+        let stderr = read(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+
+        let Some(status) = child.wait_timeout(self.timeout)? else {
+            child.kill()?;
+            child.wait()?;
+            return Err(ProcessError::Timeout(self.timeout));
+        };
+        let stdout = stdout.join().expect("reader thread panicked")?;
+        let stderr = stderr.join().expect("reader thread panicked")?;
+        if !status.success() {
+            return Err(ProcessError::Failed(status, stderr.trim().to_string()));
+        }
+        Ok(stdout)
+    }
+}
+
+impl<A, P> SmcdelProcess<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + Ord,
+{
+    /// Check `form` with SMCDEL, producing a counterexample if it does not hold.
+    ///
+    /// Announcements that SMCDEL's text format cannot express are reduced away first.
+    pub fn try_check(&self, form: Form<A, P>) -> Result<Verdict<A, P>, ProcessError> {
+        let form = match form.smcdel() {
+            Some(_) => form,
+            None => form
+                .reduce_announcements()
+                .map_err(|_| ProcessError::Inexpressible)?,
+        };
+
+        // ask whether `form` is valid, and where it is false.
+        let neg = Form::Neg(Box::new(form.clone()));
+        let mut input = CheckInput::new(self.vocab.clone(), self.law.clone(), self.obs.clone());
+        input.jobs = vec![Job::Valid(form), Job::Where(neg)];
+        let (input, props) = input.numbered();
+
+        // agents are written as identifiers in SMCDEL's text format.
+        let mut agents = Vec::new();
+        let mut name = |ag: A| {
+            let i = match agents.iter().position(|other| *other == ag) {
+                Some(i) => i,
+                None => {
+                    agents.push(ag);
+                    agents.len() - 1
+                }
+            };
+            format!("a{i}")
+        };
+        let input = CheckInput {
+            vocab: input.vocab,
+            law: input.law.map_agents(&mut name),
+            obs: input
+                .obs
+                .into_iter()
+                .map(|(ag, ps)| (name(ag), ps))
+                .collect(),
+            jobs: input
+                .jobs
+                .into_iter()
+                .map(|job| match job {
+                    Job::Valid(form) => Job::Valid(form.map_agents(&mut name)),
+                    Job::Where(form) => Job::Where(form.map_agents(&mut name)),
+                })
+                .collect(),
+        };
+
+        let text = input.text().ok_or(ProcessError::Inexpressible)?;
+        let output = self.run(&text.to_string())?;
+        match parse_output(&output) {
+            Some(None) => Ok(Verdict::Holds),
+            Some(Some(state)) => {
+                // numbers start from `1`, in order of first occurrence.
+                let world = self.vocab.iter().map(|p| {
+                    let n = props.iter().position(|q| q == p).unwrap() as u32 + 1;
+                    (p.clone(), state.contains(&n))
+                });
+                Ok(Verdict::Fails(Counterexample::new(world.collect())))
+            }
+            None => Err(ProcessError::Output(output)),
+        }
+    }
+}
+
+/// Parse the answers to a `VALID?` job followed by a `WHERE?` job for its negation: `None` if
+/// the formula is valid, and otherwise the true propositions at some state where it is false.
+fn parse_output(output: &str) -> Option<Option<Vec<u32>>> {
+    let output = strip_escapes(output);
+    let mut lines = output.lines().map(str::trim);
+
+    lines.find(|line| line.ends_with("valid on the given structure?"))?;
+    match lines.next()? {
+        "True" => return Some(None),
+        "False" => {}
+        _ => return None,
+    }
+
+    // states are lists of their true propositions, possibly inside a list of states.
+    lines.find(|line| line.starts_with("At which states"))?;
+    let rest = lines.collect::<Vec<_>>().join(" ");
+    let end = rest.find(']')?;
+    let start = rest[..end].rfind('[')?;
+    let state = rest[start + 1..end]
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|n| !n.is_empty())
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    Some(Some(state))
+}
+
+/// Remove the ANSI escape sequences with which SMCDEL colors its answers.
+fn strip_escapes(output: &str) -> String {
+    let mut plain = String::with_capacity(output.len());
+    let mut chars = output.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.find(|c| c.is_ascii_alphabetic());
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+impl<A, P> Semantics for SmcdelProcess<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + Ord,
+{
+    type Agent = A;
+    type Prop = P;
+
+    /// Check `form` with SMCDEL.
+    ///
+    /// # Panics
+    ///
+    /// Panics if SMCDEL cannot express `form`, or fails to check it.
+    fn check(&self, form: Form<Self::Agent, Self::Prop>) -> Verdict<Self::Agent, Self::Prop> {
+        self.try_check(form).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<A, P> KnowStruct for SmcdelProcess<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + Ord,
+{
+    fn new(
+        vocab: Vec<Self::Prop>,
+        law: Form<Self::Agent, Self::Prop>,
+        obs: Vec<(Self::Agent, Vec<Self::Prop>)>,
+    ) -> Self {
+        let executable = env::var_os("SMCDEL").unwrap_or_else(|| OsString::from("smcdel"));
+        Self {
+            vocab,
+            law,
+            obs,
+            executable: executable.into(),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    fn valid(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
+        self.sat(form)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    use super::*;
+
+    type S = SmcdelProcess<&'static str, &'static str>;
+
+    /// Write a stand-in for `smcdel` that runs `script` in `dir`.
+    fn stand_in(dir: &Path, script: &str) -> PathBuf {
+        let path = dir.join("smcdel");
+        fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn structure() -> S {
+        S::new(vec!["x", "y"], Form::Top, vec![("a", vec!["x"])])
+    }
+
+    #[test]
+    fn test_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let form = Form::K("a", Box::new(Form::Prop("y")));
+
+        // the input file is passed as the first argument.
+        let script = "grep -q 'a0 knows that 2' \"$1\" || exit 1
+echo 'Is (a0 knows that 2) valid on the given structure?'
+printf '\\033[1;32mFalse\\033[0m\\n'
+echo 'At which states is ~(a0 knows that 2) true?'
+echo '[1]'
+echo '[]'";
+        let st = structure().with_executable(stand_in(dir.path(), script));
+        let verdict = st.try_check(form.clone()).unwrap();
+        let cex = verdict.counterexample().unwrap();
+        assert_eq!(cex.world.get("x"), Some(&true));
+        assert_eq!(cex.world.get("y"), Some(&false));
+
+        let script = "echo 'Is (a0 knows that 2) valid on the given structure?'
+echo True";
+        let st = structure().with_executable(stand_in(dir.path(), script));
+        assert!(st.try_check(form).unwrap().holds());
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let st = structure().with_executable(dir.path().join("missing"));
+        assert!(matches!(
+            st.try_check(Form::Top),
+            Err(ProcessError::NotFound(_))
+        ));
+
+        let st = structure()
+            .with_executable(stand_in(dir.path(), "sleep 10"))
+            .with_timeout(Duration::from_millis(100));
+        assert!(matches!(
+            st.try_check(Form::Top),
+            Err(ProcessError::Timeout(_))
+        ));
+
+        let st = structure().with_executable(stand_in(dir.path(), "echo oops >&2; exit 1"));
+        assert!(matches!(
+            st.try_check(Form::Top),
+            Err(ProcessError::Failed(_, stderr)) if stderr == "oops"
+        ));
+    }
+}
@@ -0,0 +1,550 @@
+//! SMCDEL's text input format, as read by its standalone `smcdel` executable.
+//!
+//! An input consists of a vocabulary of integer propositions, a law, the propositions observed by
+//! each agent and a list of jobs:
+//!
+//! ```text
+//! -- Two agents and a secret
+//! VARS 1,2
+//! LAW  (1 -> 2)
+//! OBS  alice: 1
+//!      bob:   2
+//! VALID?
+//!   (alice knows that 2)
+//! ```
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use epistemic::{Form, KnowStruct};
+
+/// A knowledge structure and the formulas to check on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckInput<A, P> {
+    /// The vocabulary.
+    pub vocab: Vec<P>,
+    /// The law, restricting the states of the structure.
+    pub law: Form<A, P>,
+    /// The propositions observed by each agent.
+    pub obs: Vec<(A, Vec<P>)>,
+    /// The formulas to check.
+    pub jobs: Vec<Job<A, P>>,
+}
+
+/// A formula to check on a [`CheckInput`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Job<A, P> {
+    /// Check if the formula is true at every state, written `VALID?`.
+    Valid(Form<A, P>),
+    /// List the states at which the formula is true, written `WHERE?`.
+    Where(Form<A, P>),
+}
+
+impl<A, P> CheckInput<A, P> {
+    /// Create an input without jobs.
+    pub fn new(vocab: Vec<P>, law: Form<A, P>, obs: Vec<(A, Vec<P>)>) -> Self {
+        Self {
+            vocab,
+            law,
+            obs,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Build the knowledge structure.
+    pub fn know_struct<K>(&self) -> K
+    where
+        K: KnowStruct<Agent = A, Prop = P>,
+        A: Clone,
+        P: Clone,
+    {
+        K::new(self.vocab.clone(), self.law.clone(), self.obs.clone())
+    }
+
+    /// Number the propositions from `1` in order of their first occurrence, as SMCDEL requires,
+    /// and also return the proposition of each number.
+    pub fn numbered(self) -> (CheckInput<A, u32>, Vec<P>)
+    where
+        P: PartialEq,
+    {
+        let mut props = Vec::new();
+        let mut number = |p: P| match props.iter().position(|q| *q == p) {
+            Some(i) => i as u32 + 1,
+            None => {
+                props.push(p);
+                props.len() as u32
+            }
+        };
+
+        let vocab = self.vocab.into_iter().map(&mut number).collect();
+        let law = self.law.map_props(&mut number);
+        let obs = self
+            .obs
+            .into_iter()
+            .map(|(ag, ps)| (ag, ps.into_iter().map(&mut number).collect()))
+            .collect();
+        let jobs = self
+            .jobs
+            .into_iter()
+            .map(|job| match job {
+                Job::Valid(form) => Job::Valid(form.map_props(&mut number)),
+                Job::Where(form) => Job::Where(form.map_props(&mut number)),
+            })
+            .collect();
+
+        let input = CheckInput {
+            vocab,
+            law,
+            obs,
+            jobs,
+        };
+        (input, props)
+    }
+
+    /// Format in SMCDEL's text input format, if it can express the law and every job.
+    ///
+    /// Propositions are written as they are displayed, so they should be SMCDEL's integer
+    /// variables, as given by [`CheckInput::numbered`].
+    pub fn text(&self) -> Option<Text<'_, A, P>> {
+        let expressible = |form: &Form<A, P>| form.smcdel().is_some();
+        let jobs = self.jobs.iter().all(|job| match job {
+            Job::Valid(form) | Job::Where(form) => expressible(form),
+        });
+        (expressible(&self.law) && jobs).then_some(Text(self))
+    }
+}
+
+/// A [`CheckInput`] formatted in SMCDEL's text input format.
+#[derive(Debug)]
+pub struct Text<'i, A, P>(&'i CheckInput<A, P>);
+
+impl<A, P> Display for Text<'_, A, P>
+where
+    A: Display,
+    P: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Text(input) = self;
+        // the forms were checked to be expressible by `CheckInput::text`.
+        let smcdel = |form: &Form<A, P>| form.smcdel().map(|form| form.to_string());
+
+        writeln!(f, "VARS {}", Sep(&input.vocab))?;
+        writeln!(f, "LAW  {}", smcdel(&input.law).unwrap_or_default())?;
+        write!(f, "OBS")?;
+        for (i, (ag, ps)) in input.obs.iter().enumerate() {
+            let indent = if i == 0 { "  " } else { "     " };
+            writeln!(f, "{indent}{ag}: {}", Sep(ps))?;
+        }
+        if input.obs.is_empty() {
+            writeln!(f)?;
+        }
+
+        for job in &input.jobs {
+            let (kind, form) = match job {
+                Job::Valid(form) => ("VALID?", form),
+                Job::Where(form) => ("WHERE?", form),
+            };
+            writeln!(f, "{kind}")?;
+            writeln!(f, "  {}", smcdel(form).unwrap_or_default())?;
+        }
+        Ok(())
+    }
+}
+
+/// Comma-separated items.
+struct Sep<'s, T>(&'s [T]);
+
+impl<T> Display for Sep<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, x) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error in SMCDEL's text input format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, counting from `1`.
+    pub line: usize,
+    /// A description of the error.
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse an input in SMCDEL's text input format.
+///
+/// The binary connectives `iff` and `->` bind weakest, then `|`, then `&`; negations, knowledge
+/// and announcements apply to the smallest formula that follows them.
+pub fn parse(text: &str) -> Result<CheckInput<String, u32>, ParseError> {
+    let tokens = lex(text)?;
+    Parser { tokens, pos: 0 }.input()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Int(u32),
+    Ident(String),
+    Sym(&'static str),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Int(n) => write!(f, "`{n}`"),
+            Token::Ident(x) => write!(f, "`{x}`"),
+            Token::Sym(s) => write!(f, "`{s}`"),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 12] = ["->", "(", ")", "[", "]", ",", ":", "!", "?", "~", "&", "|"];
+
+/// Split `text` into tokens, each with its line.
+fn lex(text: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.split("--").next().unwrap_or_default();
+        let mut chars: Peekable<CharIndices> = line.char_indices().peekable();
+
+        while let Some(&(start, c)) = chars.peek() {
+            let rest = &line[start..];
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_ascii_digit() {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let n = rest[..len].parse().map_err(|_| ParseError {
+                    line: line_no,
+                    message: format!("proposition `{}` is out of range", &rest[..len]),
+                })?;
+                tokens.push((Token::Int(n), line_no));
+                chars.nth(len - 1);
+            } else if c.is_ascii_alphabetic() {
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '\''))
+                    .unwrap_or(rest.len());
+                tokens.push((Token::Ident(rest[..len].to_string()), line_no));
+                chars.nth(len - 1);
+            } else if let Some(sym) = SYMBOLS.iter().find(|sym| rest.starts_with(**sym)) {
+                tokens.push((Token::Sym(sym), line_no));
+                chars.nth(sym.len() - 1);
+            } else {
+                return Err(ParseError {
+                    line: line_no,
+                    message: format!("unexpected character `{c}`"),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+type Parsed<T> = Result<T, ParseError>;
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + n).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(token, _)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn error<T>(&self, expected: &str) -> Parsed<T> {
+        let (found, line) = match self.tokens.get(self.pos) {
+            Some((token, line)) => (token.to_string(), *line),
+            None => (
+                "the end of the input".to_string(),
+                self.tokens.last().map_or(1, |(_, line)| *line),
+            ),
+        };
+        Err(ParseError {
+            line,
+            message: format!("expected {expected}, found {found}"),
+        })
+    }
+
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(Token::Sym(s)) if *s == sym)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(x)) if x == keyword)
+    }
+
+    fn sym(&mut self, sym: &str) -> Parsed<()> {
+        if !self.is_sym(sym) {
+            return self.error(&format!("`{sym}`"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Parsed<()> {
+        if !self.is_keyword(keyword) {
+            return self.error(&format!("`{keyword}`"));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn int(&mut self) -> Parsed<u32> {
+        match self.peek() {
+            Some(&Token::Int(n)) => {
+                self.pos += 1;
+                Ok(n)
+            }
+            _ => self.error("a proposition"),
+        }
+    }
+
+    fn ident(&mut self) -> Parsed<String> {
+        match self.peek() {
+            Some(Token::Ident(x)) => {
+                let x = x.clone();
+                self.pos += 1;
+                Ok(x)
+            }
+            _ => self.error("an agent"),
+        }
+    }
+
+    /// A possibly empty comma-separated list of propositions.
+    fn ints(&mut self) -> Parsed<Vec<u32>> {
+        let mut ns = Vec::new();
+        if matches!(self.peek(), Some(Token::Int(_))) {
+            ns.push(self.int()?);
+            while self.is_sym(",") {
+                self.pos += 1;
+                ns.push(self.int()?);
+            }
+        }
+        Ok(ns)
+    }
+
+    fn input(mut self) -> Parsed<CheckInput<String, u32>> {
+        self.keyword("VARS")?;
+        let vocab = self.ints()?;
+        self.keyword("LAW")?;
+        let law = self.form(0)?;
+        self.keyword("OBS")?;
+        let mut obs = Vec::new();
+        while matches!(self.peek(), Some(Token::Ident(_)))
+            && self.peek_nth(1) == Some(&Token::Sym(":"))
+        {
+            let ag = self.ident()?;
+            self.sym(":")?;
+            obs.push((ag, self.ints()?));
+        }
+
+        let mut jobs = Vec::new();
+        while self.peek().is_some() {
+            let job: fn(_) -> _ = if self.is_keyword("VALID") {
+                Job::Valid
+            } else if self.is_keyword("WHERE") {
+                Job::Where
+            } else {
+                return self.error("`VALID?` or `WHERE?`");
+            };
+            self.pos += 1;
+            self.sym("?")?;
+            jobs.push(job(self.form(0)?));
+        }
+
+        Ok(CheckInput {
+            vocab,
+            law,
+            obs,
+            jobs,
+        })
+    }
+
+    /// A formula whose binary connectives bind at least as strongly as `min_bp`.
+    fn form(&mut self, min_bp: u8) -> Parsed<Form<String, u32>> {
+        let mut lhs = self.prefix()?;
+        loop {
+            let (lbp, rbp) = match self.peek() {
+                Some(Token::Sym("->")) => (2, 1),
+                Some(Token::Ident(x)) if x == "iff" => (2, 1),
+                Some(Token::Sym("|")) => (3, 4),
+                Some(Token::Sym("&")) => (5, 6),
+                _ => return Ok(lhs),
+            };
+            if lbp < min_bp {
+                return Ok(lhs);
+            }
+
+            let op = self.next();
+            let rhs = self.form(rbp)?;
+            lhs = match op {
+                Some(Token::Sym("->")) => Form::Impl(Box::new(lhs), Box::new(rhs)),
+                Some(Token::Sym("|")) => Form::Disj(vec![lhs, rhs]),
+                Some(Token::Sym("&")) => Form::Conj(vec![lhs, rhs]),
+                _ => Form::Equiv(Box::new(lhs), Box::new(rhs)),
+            };
+        }
+    }
+
+    /// A formula without unparenthesized binary connectives.
+    fn prefix(&mut self) -> Parsed<Form<String, u32>> {
+        match self.peek() {
+            Some(Token::Int(_)) => Ok(Form::Prop(self.int()?)),
+            Some(Token::Sym("~")) => {
+                self.pos += 1;
+                Ok(Form::Neg(Box::new(self.prefix()?)))
+            }
+            Some(Token::Sym("(")) => {
+                self.pos += 1;
+                let form = self.form(0)?;
+                self.sym(")")?;
+                Ok(form)
+            }
+            Some(Token::Sym("[")) => {
+                self.pos += 1;
+                let whether = self.is_sym("?");
+                if whether {
+                    self.pos += 1;
+                }
+                self.sym("!")?;
+                let p1 = Box::new(self.form(0)?);
+                self.sym("]")?;
+                let p2 = Box::new(self.prefix()?);
+                Ok(if whether {
+                    Form::PAw(p1, p2)
+                } else {
+                    Form::PA(p1, p2)
+                })
+            }
+            Some(Token::Ident(x)) => match x.as_str() {
+                "Top" => {
+                    self.pos += 1;
+                    Ok(Form::Top)
+                }
+                "Bot" => {
+                    self.pos += 1;
+                    Ok(Form::Bot)
+                }
+                _ => self.knows(),
+            },
+            _ => self.error("a formula"),
+        }
+    }
+
+    /// `ag knows that p`, `ag knows whether p`, `ags comknow that p` or `ags comknow whether p`.
+    fn knows(&mut self) -> Parsed<Form<String, u32>> {
+        let mut ags = vec![self.ident()?];
+        while self.is_sym(",") {
+            self.pos += 1;
+            ags.push(self.ident()?);
+        }
+
+        let common = match self.peek() {
+            Some(Token::Ident(x)) if x == "knows" && ags.len() == 1 => false,
+            Some(Token::Ident(x)) if x == "comknow" => true,
+            _ => return self.error("`knows` or `comknow`"),
+        };
+        self.pos += 1;
+
+        let whether = if self.is_keyword("that") {
+            false
+        } else if self.is_keyword("whether") {
+            true
+        } else {
+            return self.error("`that` or `whether`");
+        };
+        self.pos += 1;
+
+        let p = Box::new(self.prefix()?);
+        Ok(match (common, whether) {
+            (false, false) => Form::K(ags.remove(0), p),
+            (false, true) => Form::Kw(ags.remove(0), p),
+            (true, false) => Form::CK(ags, p),
+            (true, true) => Form::CKw(ags, p),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = parse(
+            "-- Muddy children
+             VARS 1,2
+             LAW  Top
+             OBS  alice: 2
+                  bob:   1
+             VALID?
+               [ ! (1 | 2) ] (alice knows whether 1 -> ~ bob knows that 2)
+             WHERE? (1 & 2)",
+        )
+        .unwrap();
+
+        assert_eq!(input.vocab, vec![1, 2]);
+        assert_eq!(input.law, Form::Top);
+        assert_eq!(
+            input.obs,
+            vec![("alice".to_string(), vec![2]), ("bob".to_string(), vec![1])]
+        );
+        assert_eq!(input.jobs.len(), 2);
+        assert_eq!(
+            input.jobs[1],
+            Job::Where(Form::Conj(vec![Form::Prop(1), Form::Prop(2)]))
+        );
+
+        let err = parse("VARS 1\nLAW 1 &\nOBS").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (alice, bob) = ("alice".to_string(), "bob".to_string());
+        let mut input = CheckInput::new(
+            vec!["x", "y"],
+            Form::Impl(Box::new(Form::Prop("x")), Box::new(Form::Prop("y"))),
+            vec![(alice.clone(), vec!["x"]), (bob.clone(), vec!["y"])],
+        );
+        input.jobs.push(Job::Valid(Form::PAw(
+            Box::new(Form::Prop("y")),
+            Box::new(Form::CKw(vec![alice, bob], Box::new(Form::Prop("y")))),
+        )));
+
+        let (input, props) = input.numbered();
+        assert_eq!(props, vec!["x", "y"]);
+
+        let text = input.text().unwrap().to_string();
+        assert_eq!(parse(&text).unwrap(), input);
+    }
+}
@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+use crate::Form;
+
+/// An event of an [`ActionModel`].
+pub type Event = usize;
+
+/// An S5 action model, or event model.
+///
+/// Each event has a precondition, the formula that must hold for it to happen, and each agent
+/// partitions the events into those it cannot tell apart. Agents without a partition cannot
+/// distinguish any events.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionModel<A, P> {
+    /// The precondition of each event.
+    pub(crate) pre: Vec<Form<A, P>>,
+    /// The equivalence class of each event, for each agent.
+    pub(crate) relations: Vec<(A, Vec<usize>)>,
+}
+
+impl<A, P> ActionModel<A, P> {
+    /// Create an action model from the preconditions of its events, which no agent can tell
+    /// apart.
+    pub fn new(pre: Vec<Form<A, P>>) -> Self {
+        Self {
+            pre,
+            relations: Vec::new(),
+        }
+    }
+
+    /// Iterate over the events.
+    pub fn events(&self) -> Range<Event> {
+        0..self.pre.len()
+    }
+
+    /// The precondition of `event`.
+    pub fn pre(&self, event: Event) -> &Form<A, P> {
+        &self.pre[event]
+    }
+
+    /// Iterate over the agents that can distinguish some events.
+    pub fn agents(&self) -> impl Iterator<Item = &A> {
+        self.relations.iter().map(|(ag, _)| ag)
+    }
+
+    /// Borrow the agents and propositions.
+    pub fn as_ref(&self) -> ActionModel<&A, &P> {
+        ActionModel {
+            pre: self.pre.iter().map(Form::as_ref).collect(),
+            relations: self
+                .relations
+                .iter()
+                .map(|(ag, classes)| (ag, classes.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<A, P> ActionModel<A, P>
+where
+    A: PartialEq,
+{
+    /// Let `agent` distinguish the events in different blocks of `partition`.
+    ///
+    /// Events missing from the partition are distinguishable from every other event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a block has an event that is not in the model, or if the blocks overlap.
+    pub fn with_partition(mut self, agent: A, partition: Vec<Vec<Event>>) -> Self {
+        // events outside of every block are their own class.
+        let n = self.pre.len();
+        let mut classes: Vec<_> = (partition.len()..partition.len() + n).collect();
+        for (i, block) in partition.into_iter().enumerate() {
+            for e in block {
+                assert!(e < n, "event {e} is not in the action model");
+                assert!(classes[e] >= i, "event {e} is in two blocks");
+                classes[e] = i;
+            }
+        }
+
+        self.relations.retain(|(ag, _)| *ag != agent);
+        self.relations.push((agent, classes));
+        self
+    }
+
+    /// The class of `event` for `ag`; `ag` cannot tell apart the events of the same class.
+    pub fn class(&self, ag: &A, event: Event) -> usize {
+        match self.relations.iter().find(|(other, _)| other == ag) {
+            Some((_, classes)) => classes[event],
+            None => 0,
+        }
+    }
+
+    /// The semi-private announcement of whether `form` to `ags`, in which event `0` announces
+    /// `form` and event `1` its negation.
+    pub fn group(ags: Vec<A>, form: Form<A, P>) -> Self
+    where
+        A: Clone,
+        P: Clone,
+    {
+        let neg = Form::Neg(Box::new(form.clone()));
+        ags.into_iter()
+            .fold(Self::new(vec![form, neg]), |action, ag| {
+                action.with_partition(ag, vec![vec![0], vec![1]])
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_partition() {
+        let action: ActionModel<&str, &str> =
+            ActionModel::new(vec![Form::Prop("x"), Form::Prop("y"), Form::Top])
+                .with_partition("a", vec![vec![0, 1]])
+                .with_partition("b", vec![vec![0], vec![1, 2]]);
+
+        assert_eq!(action.class(&"a", 0), action.class(&"a", 1));
+        assert_ne!(action.class(&"a", 1), action.class(&"a", 2));
+        assert_ne!(action.class(&"b", 0), action.class(&"b", 1));
+        assert_eq!(action.class(&"b", 1), action.class(&"b", 2));
+        assert_eq!(action.class(&"c", 0), action.class(&"c", 2));
+        assert_eq!(action.agents().count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "event 1 is in two blocks")]
+    fn test_overlapping_partition() {
+        let _: ActionModel<&str, &str> = ActionModel::new(vec![Form::Top, Form::Bot])
+            .with_partition("a", vec![vec![0, 1], vec![1]]);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{ActionModel, Form};
+
+impl<A, P> Form<A, P>
+where
+    A: Ord,
+    P: Ord,
+{
+    /// Rewrite into a canonical form, so that formulas equal up to reordering compare equal.
+    ///
+    /// Nested conjunctions, disjunctions and exclusive disjunctions are flattened and their
+    /// operands sorted, the operands of conjunctions and disjunctions are deduplicated, the sides
+    /// of equivalences are sorted, and the agents of groups and the propositions of quantifiers
+    /// are sorted and deduplicated. Unlike [`Form::simplify`], nothing else is rewritten.
+    pub fn canonical(self) -> Self {
+        let boxed = |p: Box<Self>| Box::new(p.canonical());
+        match self {
+            Form::Top | Form::Bot | Form::Prop(_) => self,
+            Form::Neg(p) => Form::Neg(boxed(p)),
+
+            Form::Conj(ps) => {
+                let mut ps = operands(ps, |p| match p {
+                    Form::Conj(ps) => Ok(ps),
+                    p => Err(p),
+                });
+                ps.dedup();
+                junction(ps, Form::Conj)
+            }
+            Form::Disj(ps) => {
+                let mut ps = operands(ps, |p| match p {
+                    Form::Disj(ps) => Ok(ps),
+                    p => Err(p),
+                });
+                ps.dedup();
+                junction(ps, Form::Disj)
+            }
+            // repeated operands cancel out, so they are kept.
+            Form::Xor(ps) => junction(
+                operands(ps, |p| match p {
+                    Form::Xor(ps) => Ok(ps),
+                    p => Err(p),
+                }),
+                Form::Xor,
+            ),
+
+            Form::Impl(p1, p2) => Form::Impl(boxed(p1), boxed(p2)),
+            Form::Equiv(p1, p2) => {
+                let (p1, p2) = (boxed(p1), boxed(p2));
+                match p1 <= p2 {
+                    true => Form::Equiv(p1, p2),
+                    false => Form::Equiv(p2, p1),
+                }
+            }
+
+            Form::Forall(xs, p) => Form::Forall(set(xs), boxed(p)),
+            Form::Exist(xs, p) => Form::Exist(set(xs), boxed(p)),
+            Form::K(ag, p) => Form::K(ag, boxed(p)),
+            Form::Kw(ag, p) => Form::Kw(ag, boxed(p)),
+            Form::B(ag, p) => Form::B(ag, boxed(p)),
+            Form::CK(ags, p) => Form::CK(set(ags), boxed(p)),
+            Form::DK(ags, p) => Form::DK(set(ags), boxed(p)),
+            Form::CKw(ags, p) => Form::CKw(set(ags), boxed(p)),
+            Form::DKw(ags, p) => Form::DKw(set(ags), boxed(p)),
+            Form::PA(p1, p2) => Form::PA(boxed(p1), boxed(p2)),
+            Form::PAw(p1, p2) => Form::PAw(boxed(p1), boxed(p2)),
+            Form::GA(ags, p1, p2) => Form::GA(set(ags), boxed(p1), boxed(p2)),
+            Form::GAw(ags, p1, p2) => Form::GAw(set(ags), boxed(p1), boxed(p2)),
+            Form::Update(action, events, p) => {
+                let ActionModel { pre, mut relations } = *action;
+                relations.sort_by(|(ag1, _), (ag2, _)| ag1.cmp(ag2));
+                let action = ActionModel {
+                    pre: pre.into_iter().map(Self::canonical).collect(),
+                    relations,
+                };
+                Form::Update(Box::new(action), set(events), boxed(p))
+            }
+        }
+    }
+}
+
+/// The sorted canonical operands of an associative connective, splicing in those of nested
+/// occurrences.
+fn operands<A, P>(
+    ps: Vec<Form<A, P>>,
+    nested: impl Fn(Form<A, P>) -> Result<Vec<Form<A, P>>, Form<A, P>>,
+) -> Vec<Form<A, P>>
+where
+    A: Ord,
+    P: Ord,
+{
+    let mut out = Vec::with_capacity(ps.len());
+    for p in ps {
+        match nested(p.canonical()) {
+            Ok(qs) => out.extend(qs),
+            Err(p) => out.push(p),
+        }
+    }
+    out.sort();
+    out
+}
+
+/// An associative connective of `ps`, or its only operand.
+fn junction<A, P>(
+    mut ps: Vec<Form<A, P>>,
+    junct: impl FnOnce(Vec<Form<A, P>>) -> Form<A, P>,
+) -> Form<A, P> {
+    match ps.len() {
+        1 => ps.pop().unwrap(),
+        _ => junct(ps),
+    }
+}
+
+fn set<T: Ord>(mut xs: Vec<T>) -> Vec<T> {
+    xs.sort();
+    xs.dedup();
+    xs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn p(x: &'static str) -> F {
+        Form::Prop(x)
+    }
+
+    #[test]
+    fn test_canonical() {
+        let form = Form::Conj(vec![
+            Form::CK(vec!["b", "a", "b"], Box::new(p("y"))),
+            Form::Conj(vec![
+                p("x"),
+                Form::Equiv(Box::new(p("z")), Box::new(p("x"))),
+            ]),
+            p("x"),
+        ]);
+        let reordered = Form::Conj(vec![
+            p("x"),
+            Form::Equiv(Box::new(p("x")), Box::new(p("z"))),
+            Form::CK(vec!["a", "b"], Box::new(p("y"))),
+        ]);
+        assert_ne!(form, reordered);
+        assert_eq!(form.clone().canonical(), reordered.clone().canonical());
+        assert_eq!(form.canonical().canonical(), reordered.canonical());
+
+        let form = Form::Xor(vec![p("y"), Form::Xor(vec![p("x"), p("y")])]);
+        assert_eq!(form.canonical(), Form::Xor(vec![p("x"), p("y"), p("y")]));
+    }
+}
@@ -0,0 +1,508 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{ActionModel, Form};
+
+/// Format in hermit's surface syntax.
+///
+/// Constructs without a surface syntax are written `Cw[a, b: p]` and `Dw[a, b: p]` for group
+/// knowledge whether, and as in SMCDEL for announcements: `[!p] q` and `[?!p] q` for public announcements,
+/// `[a, b ! p] q` and `[a, b ?! p] q` for group announcements. Updates with an action model are
+/// written `[(p, !p; a: 0 | 1) 0, 1] q`, listing the preconditions of the events and the
+/// partition of each agent, followed by the designated events.
+impl<A, P> Display for Form<A, P>
+where
+    A: Display,
+    P: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_surface(f, Ctx::TOP)
+    }
+}
+
+/// The binding powers of the infix operators of the surface syntax.
+const IMPL: (u8, u8) = (2, 1);
+const EQUIV: (u8, u8) = (3, 4);
+const JUNCT: (u8, u8) = (5, 6);
+
+/// The operators surrounding a subformula.
+#[derive(Debug, Clone, Copy)]
+struct Ctx {
+    /// The right binding power of the preceding infix operator.
+    before: u8,
+    /// The left binding power of the following infix operator, if any.
+    after: Option<u8>,
+}
+
+impl Ctx {
+    const TOP: Ctx = Ctx {
+        before: 0,
+        after: None,
+    };
+
+    /// Check if an infix operator with binding powers `bp` must be parenthesized.
+    fn infix_parens(self, (lbp, rbp): (u8, u8)) -> bool {
+        lbp < self.before || self.after.is_some_and(|after| after >= rbp)
+    }
+
+    /// Check if a prefix operator, which extends as far right as possible, must be parenthesized.
+    fn prefix_parens(self) -> bool {
+        self.after.is_some()
+    }
+
+    /// The context of the operand of an unparenthesized prefix operator.
+    fn prefix_operand(self) -> Ctx {
+        Ctx {
+            before: 0,
+            after: self.after,
+        }
+    }
+
+    /// The context of the `i`th of `n` operands of an infix operator with binding powers `bp`.
+    fn operand(self, (lbp, rbp): (u8, u8), i: usize, n: usize) -> Ctx {
+        Ctx {
+            before: if i == 0 { self.before } else { rbp },
+            after: if i + 1 == n { self.after } else { Some(lbp) },
+        }
+    }
+}
+
+impl<A, P> Form<A, P>
+where
+    A: Display,
+    P: Display,
+{
+    fn fmt_surface(&self, f: &mut Formatter<'_>, ctx: Ctx) -> fmt::Result {
+        let parens = match self {
+            Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) if ps.len() > 1 => {
+                ctx.infix_parens(JUNCT)
+            }
+            Form::Impl(..) => ctx.infix_parens(IMPL),
+            Form::Equiv(..) => ctx.infix_parens(EQUIV),
+            Form::Forall(xs, _) | Form::Exist(xs, _) if xs.is_empty() => false,
+            Form::Neg(_)
+            | Form::Forall(..)
+            | Form::Exist(..)
+            | Form::PA(..)
+            | Form::PAw(..)
+            | Form::GA(..)
+            | Form::GAw(..)
+            | Form::Update(..) => ctx.prefix_parens(),
+            _ => false,
+        };
+
+        if parens {
+            write!(f, "(")?;
+            self.fmt_surface(f, Ctx::TOP)?;
+            return write!(f, ")");
+        }
+
+        let operand = ctx.prefix_operand();
+        match self {
+            Form::Top => write!(f, "true"),
+            Form::Bot => write!(f, "false"),
+            Form::Prop(p) => write!(f, "{p}"),
+            Form::Neg(p) => {
+                write!(f, "!")?;
+                p.fmt_surface(f, operand)
+            }
+            Form::Conj(ps) => fmt_infix(f, ctx, ps, ("&&", JUNCT), Form::Top),
+            Form::Disj(ps) => fmt_infix(f, ctx, ps, ("||", JUNCT), Form::Bot),
+            Form::Xor(ps) => fmt_infix(f, ctx, ps, ("^", JUNCT), Form::Bot),
+            Form::Impl(p1, p2) => fmt_infix(f, ctx, [&**p1, &**p2], ("->", IMPL), Form::Top),
+            Form::Equiv(p1, p2) => fmt_infix(f, ctx, [&**p1, &**p2], ("==", EQUIV), Form::Top),
+            Form::Forall(xs, p) | Form::Exist(xs, p) if xs.is_empty() => p.fmt_surface(f, ctx),
+            Form::Forall(xs, p) => {
+                write!(f, "forall {}: ", Sep(xs, ", "))?;
+                p.fmt_surface(f, operand)
+            }
+            Form::Exist(xs, p) => {
+                write!(f, "exist {}: ", Sep(xs, ", "))?;
+                p.fmt_surface(f, operand)
+            }
+            Form::K(ag, p) => write!(f, "K[{ag}: {p}]"),
+            Form::Kw(ag, p) => write!(f, "Kw[{ag}: {p}]"),
+            Form::B(ag, p) => write!(f, "B[{ag}: {p}]"),
+            Form::CK(ags, p) => write!(f, "C[{}: {p}]", Sep(ags, ", ")),
+            Form::DK(ags, p) => write!(f, "D[{}: {p}]", Sep(ags, ", ")),
+            Form::CKw(ags, p) => write!(f, "Cw[{}: {p}]", Sep(ags, ", ")),
+            Form::DKw(ags, p) => write!(f, "Dw[{}: {p}]", Sep(ags, ", ")),
+            Form::PA(p1, p2) => {
+                write!(f, "[!{p1}] ")?;
+                p2.fmt_surface(f, operand)
+            }
+            Form::PAw(p1, p2) => {
+                write!(f, "[?!{p1}] ")?;
+                p2.fmt_surface(f, operand)
+            }
+            Form::GA(ags, p1, p2) => {
+                write!(f, "[{} ! {p1}] ", Sep(ags, ", "))?;
+                p2.fmt_surface(f, operand)
+            }
+            Form::GAw(ags, p1, p2) => {
+                write!(f, "[{} ?! {p1}] ", Sep(ags, ", "))?;
+                p2.fmt_surface(f, operand)
+            }
+            Form::Update(action, events, p) => {
+                write!(f, "[{action} {}] ", Sep(events, ", "))?;
+                p.fmt_surface(f, operand)
+            }
+        }
+    }
+}
+
+impl<A, P> Display for ActionModel<A, P>
+where
+    A: Display,
+    P: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}", Sep(&self.pre, ", "))?;
+        for (ag, classes) in &self.relations {
+            let blocks: Vec<_> = blocks(classes)
+                .iter()
+                .map(|block| Sep(block, ", ").to_string())
+                .collect();
+            write!(f, "; {ag}: {}", Sep(&blocks, " | "))?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// The events of each class, in order of their first event.
+fn blocks(classes: &[usize]) -> Vec<Vec<usize>> {
+    let mut blocks: Vec<(usize, Vec<usize>)> = Vec::new();
+    for (e, &class) in classes.iter().enumerate() {
+        match blocks.iter_mut().find(|(c, _)| *c == class) {
+            Some((_, block)) => block.push(e),
+            None => blocks.push((class, vec![e])),
+        }
+    }
+    blocks.into_iter().map(|(_, block)| block).collect()
+}
+
+/// Format the operands `ps` of the infix operator `op`, or `unit` if there are none.
+fn fmt_infix<'f, A, P>(
+    f: &mut Formatter<'_>,
+    ctx: Ctx,
+    ps: impl IntoIterator<Item = &'f Form<A, P>>,
+    (op, bp): (&str, (u8, u8)),
+    unit: Form<A, P>,
+) -> fmt::Result
+where
+    A: Display + 'f,
+    P: Display + 'f,
+{
+    let ps: Vec<_> = ps.into_iter().collect();
+
+    match ps.as_slice() {
+        [] => unit.fmt_surface(f, ctx),
+        [p] => p.fmt_surface(f, ctx),
+        ps => {
+            for (i, p) in ps.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {op} ")?;
+                }
+                p.fmt_surface(f, ctx.operand(bp, i, ps.len()))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// A [`Form`] formatted as LaTeX math.
+#[derive(Debug)]
+pub struct Latex<'f, A, P>(&'f Form<A, P>);
+
+/// A [`Form`] formatted in SMCDEL's input syntax.
+#[derive(Debug)]
+pub struct Smcdel<'f, A, P>(&'f Form<A, P>);
+
+impl<A, P> Form<A, P> {
+    /// Format as LaTeX math, in the notation of the proposal.
+    pub fn latex(&self) -> Latex<'_, A, P> {
+        Latex(self)
+    }
+
+    /// Format in SMCDEL's input syntax, if it can express this formula.
+    ///
+    /// SMCDEL's input syntax has no quantifiers, belief, distributed knowledge, group announcements
+    /// or action models.
+    /// Propositions are written as they are displayed, so they should be SMCDEL's integer
+    /// variables.
+    pub fn smcdel(&self) -> Option<Smcdel<'_, A, P>> {
+        self.smcdel_expressible().then_some(Smcdel(self))
+    }
+
+    fn smcdel_expressible(&self) -> bool {
+        match self {
+            Form::Top | Form::Bot | Form::Prop(_) => true,
+            Form::Neg(p) | Form::K(_, p) | Form::Kw(_, p) | Form::CK(_, p) | Form::CKw(_, p) => {
+                p.smcdel_expressible()
+            }
+            Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) => {
+                ps.iter().all(Self::smcdel_expressible)
+            }
+            Form::Impl(p1, p2) | Form::Equiv(p1, p2) | Form::PA(p1, p2) | Form::PAw(p1, p2) => {
+                p1.smcdel_expressible() && p2.smcdel_expressible()
+            }
+            Form::Forall(..)
+            | Form::Exist(..)
+            | Form::B(..)
+            | Form::DK(..)
+            | Form::DKw(..)
+            | Form::GA(..)
+            | Form::GAw(..)
+            | Form::Update(..) => false,
+        }
+    }
+}
+
+impl<A, P> Display for Latex<'_, A, P>
+where
+    A: Display,
+    P: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Latex(form) = self;
+
+        // parenthesize compound operands.
+        let operand = |p: &Form<A, P>| match p {
+            Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) if ps.len() > 1 => {
+                format!("({})", p.latex())
+            }
+            Form::Impl(..) | Form::Equiv(..) => format!("({})", p.latex()),
+            _ => p.latex().to_string(),
+        };
+        let group = |ags: &[A]| {
+            let ags: Vec<_> = ags.iter().map(tt).collect();
+            format!("\\{{{}\\}}", ags.join(", "))
+        };
+        let infix = |ps: &[Form<A, P>], op: &str, unit: &str| match ps {
+            [] => unit.to_string(),
+            [p] => p.latex().to_string(),
+            ps => {
+                let ps: Vec<_> = ps.iter().map(operand).collect();
+                ps.join(&format!(" {op} "))
+            }
+        };
+        let vars = |xs: &[P]| {
+            let xs: Vec<_> = xs.iter().map(tt).collect();
+            xs.join(", ")
+        };
+
+        match form {
+            Form::Top => write!(f, "\\top"),
+            Form::Bot => write!(f, "\\bot"),
+            Form::Prop(p) => write!(f, "{}", tt(p)),
+            Form::Neg(p) => write!(f, "\\lnot {}", operand(p)),
+            Form::Conj(ps) => write!(f, "{}", infix(ps, "\\land", "\\top")),
+            Form::Disj(ps) => write!(f, "{}", infix(ps, "\\lor", "\\bot")),
+            Form::Xor(ps) => write!(f, "{}", infix(ps, "\\oplus", "\\bot")),
+            Form::Impl(p1, p2) => write!(f, "{} \\to {}", operand(p1), operand(p2)),
+            Form::Equiv(p1, p2) => write!(f, "{} \\leftrightarrow {}", operand(p1), operand(p2)),
+            Form::Forall(xs, p) => write!(f, "\\forall {}, {}", vars(xs), operand(p)),
+            Form::Exist(xs, p) => write!(f, "\\exists {}, {}", vars(xs), operand(p)),
+            Form::K(ag, p) => write!(f, "K_{{{}}} {}", tt(ag), operand(p)),
+            Form::Kw(ag, p) => write!(f, "K^w_{{{}}} {}", tt(ag), operand(p)),
+            Form::B(ag, p) => write!(f, "B_{{{}}} {}", tt(ag), operand(p)),
+            Form::CK(ags, p) => write!(f, "C_{{{}}} {}", group(ags), operand(p)),
+            Form::DK(ags, p) => write!(f, "D_{{{}}} {}", group(ags), operand(p)),
+            Form::CKw(ags, p) => write!(f, "C^w_{{{}}} {}", group(ags), operand(p)),
+            Form::DKw(ags, p) => write!(f, "D^w_{{{}}} {}", group(ags), operand(p)),
+            Form::PA(p1, p2) => write!(f, "[{}!] {}", p1.latex(), operand(p2)),
+            Form::PAw(p1, p2) => write!(f, "[?{}!] {}", p1.latex(), operand(p2)),
+            Form::GA(ags, p1, p2) => {
+                write!(f, "[{}!]_{{{}}} {}", p1.latex(), group(ags), operand(p2))
+            }
+            Form::GAw(ags, p1, p2) => {
+                write!(f, "[?{}!]_{{{}}} {}", p1.latex(), group(ags), operand(p2))
+            }
+            Form::Update(action, events, p) => {
+                let pre: Vec<_> = action.pre.iter().map(|p| p.latex().to_string()).collect();
+                write!(f, "[({}", pre.join(", "))?;
+                for (ag, classes) in &action.relations {
+                    let blocks: Vec<_> = blocks(classes)
+                        .iter()
+                        .map(|block| Sep(block, ", ").to_string())
+                        .collect();
+                    write!(f, "; {}: {}", tt(ag), blocks.join(" \\mid "))?;
+                }
+                write!(f, "), \\{{{}\\}}] {}", Sep(events, ", "), operand(p))
+            }
+        }
+    }
+}
+
+/// Format an identifier in typewriter font, as in the slides.
+fn tt(x: impl Display) -> String {
+    format!("\\mathtt{{{}}}", x.to_string().replace('_', "\\_"))
+}
+
+impl<A, P> Display for Smcdel<'_, A, P>
+where
+    A: Display,
+    P: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Smcdel(form) = self;
+
+        let infix = |f: &mut Formatter<'_>, ps: &[Form<A, P>], op: &str, unit: &str| match ps {
+            [] => write!(f, "{unit}"),
+            [p] => write!(f, "{}", Smcdel(p)),
+            ps => {
+                write!(f, "(")?;
+                for (i, p) in ps.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " {op} ")?;
+                    }
+                    write!(f, "{}", Smcdel(p))?;
+                }
+                write!(f, ")")
+            }
+        };
+
+        match form {
+            Form::Top => write!(f, "Top"),
+            Form::Bot => write!(f, "Bot"),
+            Form::Prop(p) => write!(f, "{p}"),
+            Form::Neg(p) => write!(f, "~ {}", Smcdel(p)),
+            Form::Conj(ps) => infix(f, ps, "&", "Top"),
+            Form::Disj(ps) => infix(f, ps, "|", "Bot"),
+            Form::Xor(ps) => fmt_smcdel_xor(f, ps),
+            Form::Impl(p1, p2) => write!(f, "({} -> {})", Smcdel(p1), Smcdel(p2)),
+            Form::Equiv(p1, p2) => write!(f, "({} iff {})", Smcdel(p1), Smcdel(p2)),
+            Form::K(ag, p) => write!(f, "({ag} knows that {})", Smcdel(p)),
+            Form::Kw(ag, p) => write!(f, "({ag} knows whether {})", Smcdel(p)),
+            Form::CK(ags, p) => write!(f, "({} comknow that {})", Sep(ags, ","), Smcdel(p)),
+            Form::CKw(ags, p) => write!(f, "({} comknow whether {})", Sep(ags, ","), Smcdel(p)),
+            Form::PA(p1, p2) => write!(f, "[ ! {} ] {}", Smcdel(p1), Smcdel(p2)),
+            Form::PAw(p1, p2) => write!(f, "[ ? ! {} ] {}", Smcdel(p1), Smcdel(p2)),
+            Form::Forall(..)
+            | Form::Exist(..)
+            | Form::B(..)
+            | Form::DK(..)
+            | Form::DKw(..)
+            | Form::GA(..)
+            | Form::GAw(..)
+            | Form::Update(..) => unreachable!("checked by `Form::smcdel`"),
+        }
+    }
+}
+
+/// Format an exclusive disjunction as nested negated equivalences, which SMCDEL does have.
+fn fmt_smcdel_xor<A, P>(f: &mut Formatter<'_>, ps: &[Form<A, P>]) -> fmt::Result
+where
+    A: Display,
+    P: Display,
+{
+    match ps {
+        [] => write!(f, "Bot"),
+        [p] => write!(f, "{}", Smcdel(p)),
+        [p, ps @ ..] => {
+            write!(f, "~ ({} iff ", Smcdel(p))?;
+            fmt_smcdel_xor(f, ps)?;
+            write!(f, ")")
+        }
+    }
+}
+
+/// Items formatted with a separator.
+struct Sep<'s, T>(&'s [T], &'s str);
+
+impl<T> Display for Sep<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Sep(xs, sep) = *self;
+        for (i, x) in xs.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{sep}")?;
+            }
+            write!(f, "{x}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn p(x: &'static str) -> F {
+        Form::Prop(x)
+    }
+
+    fn neg(p: F) -> F {
+        Form::Neg(Box::new(p))
+    }
+
+    #[test]
+    fn test_surface() {
+        let form = Form::Conj(vec![
+            neg(p("x")),
+            Form::Impl(Box::new(p("y")), Box::new(p("z"))),
+            p("w"),
+        ]);
+        assert_eq!(form.to_string(), "(!x) && (y -> z) && w");
+
+        let form = Form::Impl(
+            Box::new(Form::Disj(vec![p("x"), p("y")])),
+            Box::new(Form::Impl(Box::new(p("z")), Box::new(neg(p("w"))))),
+        );
+        assert_eq!(form.to_string(), "x || y -> z -> !w");
+
+        let form = Form::K("a", Box::new(Form::CK(vec!["a", "b"], Box::new(p("x")))));
+        assert_eq!(form.to_string(), "K[a: C[a, b: x]]");
+    }
+
+    #[test]
+    fn test_latex() {
+        let form = Form::GA(
+            vec!["db"],
+            Box::new(p("pwd")),
+            Box::new(neg(Form::K("db", Box::new(p("pwd_hash"))))),
+        );
+        assert_eq!(
+            form.latex().to_string(),
+            "[\\mathtt{pwd}!]_{\\{\\mathtt{db}\\}} \\lnot K_{\\mathtt{db}} \\mathtt{pwd\\_hash}"
+        );
+    }
+
+    #[test]
+    fn test_smcdel() {
+        let form: Form<&str, u32> = Form::PA(
+            Box::new(Form::Disj(vec![Form::Prop(1), Form::Prop(2)])),
+            Box::new(Form::Neg(Box::new(Form::K(
+                "alice",
+                Box::new(Form::Prop(1)),
+            )))),
+        );
+        assert_eq!(
+            form.smcdel().unwrap().to_string(),
+            "[ ! (1 | 2) ] ~ (alice knows that 1)"
+        );
+
+        let form: Form<&str, u32> = Form::DK(vec!["alice"], Box::new(Form::Prop(1)));
+        assert!(form.smcdel().is_none());
+    }
+
+    #[test]
+    fn test_update() {
+        let action = ActionModel::new(vec![p("x"), neg(p("x")), Form::Top])
+            .with_partition("a", vec![vec![0], vec![1]])
+            .with_partition("b", vec![vec![0, 1]]);
+        let form = Form::Update(
+            Box::new(action),
+            vec![0, 1],
+            Box::new(Form::K("a", Box::new(p("x")))),
+        );
+        assert_eq!(
+            form.to_string(),
+            "[(x, !x, true; a: 0 | 1 | 2; b: 0, 1 | 2) 0, 1] K[a: x]"
+        );
+        assert!(form.smcdel().is_none());
+    }
+}
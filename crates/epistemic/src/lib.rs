@@ -1,12 +1,32 @@
-use std::iter;
+mod action;
+mod canonical;
+mod display;
+mod metrics;
+mod polarity;
+mod reduce;
+mod simplify;
+mod verdict;
+mod visit;
 
-use iter_tree::Tree;
+pub use action::{ActionModel, Event};
+pub use display::{Latex, Smcdel};
+pub use metrics::{Measure, Metrics};
+pub use polarity::Polarity;
+pub use reduce::ReduceError;
+pub use verdict::*;
+pub use visit::{fold_form, walk_form, Fold, Visitor};
 
 pub trait Semantics {
     type Agent;
     type Prop;
 
-    fn sat(&self, form: Form<Self::Agent, Self::Prop>) -> bool;
+    /// Check if `form` is satisfied.
+    fn sat(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
+        self.check(form).holds()
+    }
+
+    /// Check if `form` is satisfied, producing a counterexample if it is not.
+    fn check(&self, form: Form<Self::Agent, Self::Prop>) -> Verdict<Self::Agent, Self::Prop>;
 }
 
 pub trait KnowStruct: Semantics {
@@ -15,9 +35,31 @@ pub trait KnowStruct: Semantics {
         law: Form<Self::Agent, Self::Prop>,
         obs: Vec<(Self::Agent, Vec<Self::Prop>)>,
     ) -> Self;
+
+    /// Check if `form` is true at every state of the structure.
+    fn valid(&self, form: Form<Self::Agent, Self::Prop>) -> bool;
+
+    /// Check if `form` is true at some state of the structure.
+    fn satisfiable(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
+        !self.valid(Form::Neg(Box::new(form)))
+    }
+
+    /// Check if `p` and `q` are true at the same states of the structure.
+    fn equivalent(
+        &self,
+        p: Form<Self::Agent, Self::Prop>,
+        q: Form<Self::Agent, Self::Prop>,
+    ) -> bool {
+        self.valid(Form::Equiv(Box::new(p), Box::new(q)))
+    }
+
+    /// Check if `q` is true at every state of the structure at which `p` is.
+    fn entails(&self, p: Form<Self::Agent, Self::Prop>, q: Form<Self::Agent, Self::Prop>) -> bool {
+        self.valid(Form::Impl(Box::new(p), Box::new(q)))
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Form<A, P> {
     Top,
@@ -32,6 +74,8 @@ pub enum Form<A, P> {
     Forall(Vec<P>, Box<Self>),
     Exist(Vec<P>, Box<Self>),
     K(A, Box<Self>),
+    Kw(A, Box<Self>),
+    B(A, Box<Self>),
     CK(Vec<A>, Box<Self>),
     DK(Vec<A>, Box<Self>),
     CKw(Vec<A>, Box<Self>),
@@ -40,86 +84,13 @@ pub enum Form<A, P> {
     PAw(Box<Self>, Box<Self>),
     GA(Vec<A>, Box<Self>, Box<Self>),
     GAw(Vec<A>, Box<Self>, Box<Self>),
-}
-
-impl<A, P> Form<A, P> {
-    /// Iterate over the propositions.
-    pub fn vocab<'a>(&'a self) -> Box<dyn Iterator<Item = &'a P> + 'a> {
-        match self.vocab_tree() {
-            Some(tree) => Box::new(tree.into_iter()),
-            None => Box::new(iter::empty()),
-        }
-    }
-
-    fn vocab_tree(&self) -> Option<Tree<&P>> {
-        match self {
-            Form::Top | Form::Bot => None,
-            Form::Prop(b) => Some(Tree::Leaf(b)),
-            Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) => {
-                let ns = ps.iter().filter_map(Self::vocab_tree).collect();
-                Some(Tree::Node(ns))
-            }
-            Form::Neg(p)
-            | Form::Forall(_, p)
-            | Form::Exist(_, p)
-            | Form::K(_, p)
-            | Form::CK(_, p)
-            | Form::DK(_, p)
-            | Form::CKw(_, p)
-            | Form::DKw(_, p) => p.vocab_tree().map(|n| Tree::Node(vec![n])),
-            Form::Impl(p1, p2)
-            | Form::Equiv(p1, p2)
-            | Form::PA(p1, p2)
-            | Form::PAw(p1, p2)
-            | Form::GA(_, p1, p2)
-            | Form::GAw(_, p1, p2) => {
-                let n1 = p1.vocab_tree()?;
-                let n2 = p2.vocab_tree()?;
-                Some(Tree::Node(vec![n1, n2]))
-            }
-        }
-    }
+    /// The product update with an action model, true if the formula holds after each of the
+    /// events whose precondition is true.
+    Update(Box<ActionModel<A, P>>, Vec<Event>, Box<Self>),
 }
 
 impl<'p, A, P> From<&'p Form<A, P>> for Form<&'p A, &'p P> {
     fn from(form: &'p Form<A, P>) -> Self {
-        match form {
-            Form::Top => Form::Top,
-            Form::Bot => Form::Bot,
-            Form::Prop(b) => Form::Prop(b),
-            Form::Neg(p) => Form::Neg(Box::new(p.as_ref().into())),
-            Form::Conj(ps) => Form::Conj(ps.iter().map(Into::into).collect()),
-            Form::Disj(ps) => Form::Conj(ps.iter().map(Into::into).collect()),
-            Form::Xor(ps) => Form::Conj(ps.iter().map(Into::into).collect()),
-            Form::Impl(p1, p2) => {
-                Form::Impl(Box::new(p1.as_ref().into()), Box::new(p2.as_ref().into()))
-            }
-            Form::Equiv(p1, p2) => {
-                Form::Equiv(Box::new(p1.as_ref().into()), Box::new(p2.as_ref().into()))
-            }
-            Form::Forall(xs, p) => Form::Forall(xs.iter().collect(), Box::new(p.as_ref().into())),
-            Form::Exist(xs, p) => Form::Exist(xs.iter().collect(), Box::new(p.as_ref().into())),
-            Form::K(ag, p) => Form::K(ag, Box::new(p.as_ref().into())),
-            Form::CK(ags, p) => Form::CK(ags.iter().collect(), Box::new(p.as_ref().into())),
-            Form::DK(ags, p) => Form::DK(ags.iter().collect(), Box::new(p.as_ref().into())),
-            Form::CKw(ags, p) => Form::CKw(ags.iter().collect(), Box::new(p.as_ref().into())),
-            Form::DKw(ags, p) => Form::DKw(ags.iter().collect(), Box::new(p.as_ref().into())),
-            Form::PA(p1, p2) => {
-                Form::PA(Box::new(p1.as_ref().into()), Box::new(p2.as_ref().into()))
-            }
-            Form::PAw(p1, p2) => {
-                Form::PAw(Box::new(p1.as_ref().into()), Box::new(p2.as_ref().into()))
-            }
-            Form::GA(ags, p1, p2) => Form::GA(
-                ags.iter().collect(),
-                Box::new(p1.as_ref().into()),
-                Box::new(p2.as_ref().into()),
-            ),
-            Form::GAw(ags, p1, p2) => Form::GAw(
-                ags.iter().collect(),
-                Box::new(p1.as_ref().into()),
-                Box::new(p2.as_ref().into()),
-            ),
-        }
+        form.as_ref()
     }
 }
@@ -0,0 +1,187 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{walk_form, Form, Visitor};
+
+/// Measures of the size of formulas, which predict the cost of checking them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metrics {
+    /// The number of nodes.
+    pub size: usize,
+    /// The greatest nesting of knowledge and belief modalities, of any kind.
+    pub modal_depth: usize,
+    /// The greatest nesting of common knowledge.
+    pub common_depth: usize,
+    /// The number of distinct agents mentioned.
+    pub agents: usize,
+    /// The number of distinct propositions, free or bound.
+    pub vocab: usize,
+    /// The greatest nesting of announcements and updates.
+    pub announcement_depth: usize,
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "size {}, modal depth {}, common knowledge depth {}, announcement depth {}, {} agents, {} propositions",
+            self.size,
+            self.modal_depth,
+            self.common_depth,
+            self.announcement_depth,
+            self.agents,
+            self.vocab,
+        )
+    }
+}
+
+impl<A, P> Form<A, P>
+where
+    A: Ord,
+    P: Ord,
+{
+    /// Measure the formula.
+    pub fn metrics(&self) -> Metrics {
+        Self::metrics_all([self])
+    }
+
+    /// Measure formulas together, as if they were conjuncts.
+    pub fn metrics_all<'f>(forms: impl IntoIterator<Item = &'f Self>) -> Metrics
+    where
+        A: 'f,
+        P: 'f,
+    {
+        let mut measure = Measure::default();
+        forms.into_iter().for_each(|form| measure.visit_form(form));
+        measure.metrics()
+    }
+}
+
+/// The bookkeeping of measuring formulas, as a [`Visitor`] of [`Form`] and for the visitors of
+/// other kinds of formulas.
+pub struct Measure<'f, A, P> {
+    metrics: Metrics,
+    /// The depths of the current subformula.
+    depth: Metrics,
+    agents: BTreeSet<&'f A>,
+    vocab: BTreeSet<&'f P>,
+}
+
+impl<A, P> Default for Measure<'_, A, P> {
+    fn default() -> Self {
+        Self {
+            metrics: Metrics::default(),
+            depth: Metrics::default(),
+            agents: BTreeSet::new(),
+            vocab: BTreeSet::new(),
+        }
+    }
+}
+
+impl<'f, A, P> Measure<'f, A, P>
+where
+    A: Ord,
+    P: Ord,
+{
+    /// Count a node that nests its subformulas deeper by `modal`, `common` and `announcement`,
+    /// returning the depths to [`Measure::leave`] it with.
+    pub fn enter(&mut self, modal: usize, common: usize, announcement: usize) -> Metrics {
+        let outer = self.depth;
+        self.depth.modal_depth += modal;
+        self.depth.common_depth += common;
+        self.depth.announcement_depth += announcement;
+
+        let metrics = &mut self.metrics;
+        metrics.size += 1;
+        metrics.modal_depth = metrics.modal_depth.max(self.depth.modal_depth);
+        metrics.common_depth = metrics.common_depth.max(self.depth.common_depth);
+        metrics.announcement_depth = metrics
+            .announcement_depth
+            .max(self.depth.announcement_depth);
+        outer
+    }
+
+    /// Leave the node entered at the depths `outer`.
+    pub fn leave(&mut self, outer: Metrics) {
+        self.depth = outer;
+    }
+
+    /// Count the mentioned agents.
+    pub fn agents(&mut self, ags: impl IntoIterator<Item = &'f A>) {
+        self.agents.extend(ags);
+    }
+
+    /// Count the mentioned propositions.
+    pub fn vocab(&mut self, ps: impl IntoIterator<Item = &'f P>) {
+        self.vocab.extend(ps);
+    }
+
+    /// The measures of everything counted so far.
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            agents: self.agents.len(),
+            vocab: self.vocab.len(),
+            ..self.metrics
+        }
+    }
+}
+
+impl<'f, A, P> Visitor<'f, A, P> for Measure<'f, A, P>
+where
+    A: Ord,
+    P: Ord,
+{
+    fn visit_form(&mut self, form: &'f Form<A, P>) {
+        // the modal, common knowledge and announcement nesting added by `form`.
+        let (modal, common, announcement) = match form {
+            Form::K(..) | Form::Kw(..) | Form::B(..) | Form::DK(..) | Form::DKw(..) => (1, 0, 0),
+            Form::CK(..) | Form::CKw(..) => (1, 1, 0),
+            Form::PA(..) | Form::PAw(..) | Form::GA(..) | Form::GAw(..) | Form::Update(..) => {
+                (0, 0, 1)
+            }
+            _ => (0, 0, 0),
+        };
+
+        let outer = self.enter(modal, common, announcement);
+        walk_form(self, form);
+        self.leave(outer);
+    }
+
+    fn visit_agent(&mut self, ag: &'f A) {
+        self.agents([ag]);
+    }
+
+    fn visit_prop(&mut self, p: &'f P) {
+        self.vocab([p]);
+    }
+
+    fn visit_binder(&mut self, xs: &'f [P]) {
+        self.vocab(xs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let form: Form<&str, &str> = Form::Conj(vec![
+            Form::K(
+                "a",
+                Box::new(Form::CK(vec!["a", "b"], Box::new(Form::Prop("x")))),
+            ),
+            Form::PA(Box::new(Form::Prop("y")), Box::new(Form::Prop("x"))),
+        ]);
+        let metrics = Metrics {
+            size: 7,
+            modal_depth: 2,
+            common_depth: 1,
+            agents: 2,
+            vocab: 2,
+            announcement_depth: 1,
+        };
+        assert_eq!(form.metrics(), metrics);
+    }
+}
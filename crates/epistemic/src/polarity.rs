@@ -0,0 +1,133 @@
+use crate::Form;
+
+/// The polarity of a position in a formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Polarity {
+    /// Under an even number of negations, so the formula is monotone in the position.
+    Positive,
+    /// Under an odd number of negations, so the formula is antitone in the position.
+    Negative,
+    /// Both positive and negative, as under an equivalence.
+    Mixed,
+}
+
+impl Polarity {
+    /// The polarity of the position under a negation.
+    pub fn flip(self) -> Self {
+        match self {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+            Polarity::Mixed => Polarity::Mixed,
+        }
+    }
+
+    /// Check if the position is positive, possibly also negative.
+    pub fn is_positive(self) -> bool {
+        matches!(self, Polarity::Positive | Polarity::Mixed)
+    }
+}
+
+impl<A, P> Form<A, P> {
+    /// The knowledge modalities of the formula, with the polarity of their position.
+    ///
+    /// Knowing whether is positive knowledge of either the formula or its negation.
+    pub fn knowledge(&self) -> Vec<(Polarity, &Self)> {
+        let mut out = Vec::new();
+        self.knowledge_in(Polarity::Positive, &mut out);
+        out
+    }
+
+    /// The knowledge modalities in positive position, which assert that an agent knows something.
+    pub fn positive_knowledge(&self) -> Vec<&Self> {
+        self.knowledge()
+            .into_iter()
+            .filter(|(pol, _)| pol.is_positive())
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    fn knowledge_in<'f>(&'f self, pol: Polarity, out: &mut Vec<(Polarity, &'f Self)>) {
+        match self {
+            Form::Top | Form::Bot | Form::Prop(_) => {}
+            Form::Neg(p) => p.knowledge_in(pol.flip(), out),
+            Form::Conj(ps) | Form::Disj(ps) => ps.iter().for_each(|p| p.knowledge_in(pol, out)),
+            Form::Xor(ps) => ps.iter().for_each(|p| p.knowledge_in(Polarity::Mixed, out)),
+            Form::Impl(p1, p2) => {
+                p1.knowledge_in(pol.flip(), out);
+                p2.knowledge_in(pol, out);
+            }
+            Form::Equiv(p1, p2) => {
+                p1.knowledge_in(Polarity::Mixed, out);
+                p2.knowledge_in(Polarity::Mixed, out);
+            }
+            Form::Forall(_, p) | Form::Exist(_, p) => p.knowledge_in(pol, out),
+            // beliefs may be revised by what the agent observes, so they are both.
+            Form::B(_, p) => {
+                out.push((Polarity::Mixed, self));
+                p.knowledge_in(pol, out);
+            }
+            Form::K(_, p) | Form::CK(_, p) | Form::DK(_, p) => {
+                out.push((pol, self));
+                p.knowledge_in(pol, out);
+            }
+            Form::Kw(_, p) | Form::CKw(_, p) | Form::DKw(_, p) => {
+                out.push((pol, self));
+                p.knowledge_in(Polarity::Mixed, out);
+            }
+            // the announced formula restricts the states, so it is both.
+            Form::PA(p1, p2) | Form::PAw(p1, p2) | Form::GA(_, p1, p2) | Form::GAw(_, p1, p2) => {
+                p1.knowledge_in(Polarity::Mixed, out);
+                p2.knowledge_in(pol, out);
+            }
+            Form::Update(action, _, p) => {
+                for event in action.events() {
+                    action.pre(event).knowledge_in(Polarity::Mixed, out);
+                }
+                p.knowledge_in(pol, out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn k(ag: &'static str, x: &'static str) -> F {
+        Form::K(ag, Box::new(Form::Prop(x)))
+    }
+
+    #[test]
+    fn test_knowledge() {
+        // ¬(K a x → K b y) ∧ (K c z ↔ ⊤)
+        let form = Form::Conj(vec![
+            Form::Neg(Box::new(Form::Impl(
+                Box::new(k("a", "x")),
+                Box::new(k("b", "y")),
+            ))),
+            Form::Equiv(Box::new(k("c", "z")), Box::new(Form::Top)),
+        ]);
+        assert_eq!(
+            form.knowledge(),
+            vec![
+                (Polarity::Positive, &k("a", "x")),
+                (Polarity::Negative, &k("b", "y")),
+                (Polarity::Mixed, &k("c", "z")),
+            ]
+        );
+        assert_eq!(form.positive_knowledge(), vec![&k("a", "x"), &k("c", "z")]);
+
+        // ¬B a (K b y)
+        let belief = Form::B("a", Box::new(k("b", "y")));
+        let form = Form::Neg(Box::new(belief.clone()));
+        assert_eq!(
+            form.knowledge(),
+            vec![
+                (Polarity::Mixed, &belief),
+                (Polarity::Negative, &k("b", "y")),
+            ]
+        );
+    }
+}
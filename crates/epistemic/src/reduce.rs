@@ -0,0 +1,241 @@
+use std::slice;
+
+use crate::{fold_form, ActionModel, Event, Fold, Form};
+
+/// A formula under an announcement, into which the announcement cannot be reduced.
+#[derive(Debug, Clone)]
+pub enum ReduceError<A, P> {
+    /// Common knowledge, which would need relativized common knowledge.
+    Common(Form<A, P>),
+    /// Belief, which would need conditional belief.
+    Belief(Form<A, P>),
+    /// A quantifier binding a proposition of the announcement.
+    Captured(Form<A, P>),
+}
+
+impl<A, P> Form<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + PartialEq,
+{
+    /// Rewrite announcements and updates into equivalent announcement-free formulas, by the
+    /// reduction axioms of dynamic epistemic logic.
+    ///
+    /// Announcements are reduced inside-out. The reduction is not simplified, and may be
+    /// exponentially larger than the original formula.
+    pub fn reduce_announcements(self) -> Result<Self, ReduceError<A, P>> {
+        Reduce.fold_form(self)
+    }
+}
+
+/// A [`Fold`] reducing each announcement, once its children are announcement-free.
+struct Reduce;
+
+impl<A, P> Fold<A, P> for Reduce
+where
+    A: Clone + PartialEq,
+    P: Clone + PartialEq,
+{
+    type Agent = A;
+    type Prop = P;
+    type Error = ReduceError<A, P>;
+
+    fn fold_form(&mut self, form: Form<A, P>) -> Result<Form<A, P>, Self::Error> {
+        match fold_form(self, form)? {
+            Form::PA(p1, p2) => Events::new(vec![*p1], Obs::All).reduce(&[0], *p2),
+            Form::PAw(p1, p2) => Events::whether(*p1, Obs::All).reduce(&[0, 1], *p2),
+            Form::GA(ags, p1, p2) => Events::whether(*p1, Obs::Group(ags)).reduce(&[0], *p2),
+            Form::GAw(ags, p1, p2) => Events::whether(*p1, Obs::Group(ags)).reduce(&[0, 1], *p2),
+            Form::Update(action, events, p) => {
+                let ActionModel { pre, relations } = *action;
+                Events::new(pre, Obs::Classes(relations)).reduce(&events, *p)
+            }
+            form => Ok(form),
+        }
+    }
+
+    fn fold_agent(&mut self, ag: A) -> Result<A, Self::Error> {
+        Ok(ag)
+    }
+
+    fn fold_prop(&mut self, p: P) -> Result<P, Self::Error> {
+        Ok(p)
+    }
+}
+
+/// The events of an announcement, with their preconditions.
+struct Events<A, P> {
+    pre: Vec<Form<A, P>>,
+    obs: Obs<A>,
+}
+
+/// The events that each agent can tell apart.
+enum Obs<A> {
+    /// Every agent tells apart every event.
+    All,
+    /// The agents of the group tell apart every event, and the others none.
+    Group(Vec<A>),
+    /// The equivalence class of each event, for each agent, as in an [`ActionModel`].
+    Classes(Vec<(A, Vec<usize>)>),
+}
+
+impl<A, P> Events<A, P>
+where
+    A: Clone + PartialEq,
+    P: Clone + PartialEq,
+{
+    fn new(pre: Vec<Form<A, P>>, obs: Obs<A>) -> Self {
+        Self { pre, obs }
+    }
+
+    /// The announcement of whether `form`, in which event `0` announces `form` and event `1`
+    /// its negation.
+    fn whether(form: Form<A, P>, obs: Obs<A>) -> Self {
+        let neg = Form::Neg(Box::new(form.clone()));
+        Self::new(vec![form, neg], obs)
+    }
+
+    /// The events that `ags` together cannot tell apart from `e`.
+    fn same(&self, ags: &[A], e: Event) -> Vec<Event> {
+        let events = 0..self.pre.len();
+        match &self.obs {
+            Obs::All => vec![e],
+            Obs::Group(group) if ags.iter().any(|ag| group.contains(ag)) => vec![e],
+            Obs::Group(_) => events.collect(),
+            Obs::Classes(relations) => {
+                let class = |ag: &A, e: Event| {
+                    relations
+                        .iter()
+                        .find(|(other, _)| other == ag)
+                        .map_or(0, |(_, classes)| classes[e])
+                };
+                events
+                    .filter(|&f| ags.iter().all(|ag| class(ag, f) == class(ag, e)))
+                    .collect()
+            }
+        }
+    }
+
+    /// The announcement-free formula that `form` holds after any of `designated` that happens.
+    fn reduce(
+        &self,
+        designated: &[Event],
+        form: Form<A, P>,
+    ) -> Result<Form<A, P>, ReduceError<A, P>> {
+        let ps = designated
+            .iter()
+            .map(|&e| {
+                let after = self.after(e, form.clone())?;
+                Ok(Form::Impl(Box::new(self.pre[e].clone()), Box::new(after)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(match <[_; 1]>::try_from(ps) {
+            Ok([p]) => p,
+            Err(ps) => Form::Conj(ps),
+        })
+    }
+
+    /// The announcement-free formula that is true where `form` is true after `e` happens.
+    fn after(&self, e: Event, form: Form<A, P>) -> Result<Form<A, P>, ReduceError<A, P>> {
+        let after = |p: Form<A, P>| self.after(e, p).map(Box::new);
+        let all = |ps: Vec<Form<A, P>>| {
+            ps.into_iter()
+                .map(|p| self.after(e, p))
+                .collect::<Result<_, _>>()
+        };
+        // what `ags` know after `e` is what they knew of each event they cannot tell apart.
+        let know = |ags: &[A], p: Form<A, P>, modal: &dyn Fn(Box<Form<A, P>>) -> Form<A, P>| {
+            let ps = self
+                .same(ags, e)
+                .into_iter()
+                .map(|f| {
+                    let after = self.after(f, p.clone())?;
+                    let pre = Box::new(self.pre[f].clone());
+                    Ok(modal(Box::new(Form::Impl(pre, Box::new(after)))))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Form::Conj(ps))
+        };
+        match form {
+            Form::Top | Form::Bot | Form::Prop(_) => Ok(form),
+            Form::Neg(p) => Ok(Form::Neg(after(*p)?)),
+            Form::Conj(ps) => Ok(Form::Conj(all(ps)?)),
+            Form::Disj(ps) => Ok(Form::Disj(all(ps)?)),
+            Form::Xor(ps) => Ok(Form::Xor(all(ps)?)),
+            Form::Impl(p1, p2) => Ok(Form::Impl(after(*p1)?, after(*p2)?)),
+            Form::Equiv(p1, p2) => Ok(Form::Equiv(after(*p1)?, after(*p2)?)),
+            Form::Forall(ref xs, _) | Form::Exist(ref xs, _)
+                if self
+                    .pre
+                    .iter()
+                    .any(|pre| pre.vocab().any(|p| xs.contains(p))) =>
+            {
+                Err(ReduceError::Captured(form))
+            }
+            Form::Forall(xs, p) => Ok(Form::Forall(xs, after(*p)?)),
+            Form::Exist(xs, p) => Ok(Form::Exist(xs, after(*p)?)),
+            Form::K(ag, p) => know(slice::from_ref(&ag), *p, &|p| Form::K(ag.clone(), p)),
+            Form::DK(ags, p) => know(&ags, *p, &|p| Form::DK(ags.clone(), p)),
+            Form::Kw(ag, p) => self.after(e, whether(p, |p| Form::K(ag.clone(), p))),
+            Form::DKw(ags, p) => self.after(e, whether(p, |p| Form::DK(ags.clone(), p))),
+            Form::CK(..) | Form::CKw(..) => Err(ReduceError::Common(form)),
+            Form::B(..) => Err(ReduceError::Belief(form)),
+            Form::PA(..) | Form::PAw(..) | Form::GA(..) | Form::GAw(..) | Form::Update(..) => {
+                unreachable!("announcements are reduced inside-out")
+            }
+        }
+    }
+}
+
+/// Knowing whether `p` as knowing `p` or knowing its negation.
+fn whether<A, P>(p: Box<Form<A, P>>, modal: impl Fn(Box<Form<A, P>>) -> Form<A, P>) -> Form<A, P>
+where
+    P: Clone,
+    A: Clone,
+{
+    let neg = Box::new(Form::Neg(p.clone()));
+    Form::Disj(vec![modal(p), modal(neg)])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn p(x: &'static str) -> F {
+        Form::Prop(x)
+    }
+
+    #[test]
+    fn test_reduce() {
+        let form = Form::PA(Box::new(p("x")), Box::new(Form::K("a", Box::new(p("y")))));
+        let reduced = Form::Impl(
+            Box::new(p("x")),
+            Box::new(Form::Conj(vec![Form::K(
+                "a",
+                Box::new(Form::Impl(Box::new(p("x")), Box::new(p("y")))),
+            )])),
+        );
+        assert_eq!(form.reduce_announcements().unwrap(), reduced);
+
+        let form = Form::GAw(
+            vec!["a"],
+            Box::new(p("x")),
+            Box::new(Form::CK(vec!["a"], Box::new(p("x")))),
+        );
+        assert!(matches!(
+            form.reduce_announcements(),
+            Err(ReduceError::Common(_))
+        ));
+
+        let form = Form::PA(
+            Box::new(p("x")),
+            Box::new(Form::Exist(vec!["x"], Box::new(p("x")))),
+        );
+        assert!(matches!(
+            form.reduce_announcements(),
+            Err(ReduceError::Captured(_))
+        ));
+    }
+}
@@ -0,0 +1,350 @@
+use std::iter;
+
+use crate::{ActionModel, Form};
+
+impl<A, P> Form<A, P>
+where
+    A: PartialEq,
+    P: PartialEq,
+{
+    /// Simplify into an equivalent, usually smaller, formula.
+    ///
+    /// Nested conjunctions, disjunctions and exclusive disjunctions are flattened, constants are
+    /// folded, double negations are eliminated and duplicate conjuncts and disjuncts are removed.
+    pub fn simplify(self) -> Self {
+        match self {
+            Form::Top | Form::Bot | Form::Prop(_) => self,
+            Form::Neg(p) => p.simplify().negate(),
+
+            Form::Conj(ps) => junction(
+                ps,
+                Form::Top,
+                Form::Bot,
+                |p| match p {
+                    Form::Conj(ps) => Ok(ps),
+                    p => Err(p),
+                },
+                Form::Conj,
+            ),
+            Form::Disj(ps) => junction(
+                ps,
+                Form::Bot,
+                Form::Top,
+                |p| match p {
+                    Form::Disj(ps) => Ok(ps),
+                    p => Err(p),
+                },
+                Form::Disj,
+            ),
+            Form::Xor(ps) => {
+                let mut flipped = false;
+                let mut xs = Vec::new();
+                for p in flatten(ps, |p| match p {
+                    Form::Xor(ps) => Ok(ps),
+                    p => Err(p),
+                }) {
+                    match p {
+                        Form::Top => flipped = !flipped,
+                        Form::Bot => {}
+                        p => xs.push(p),
+                    }
+                }
+
+                let form = match xs.len() {
+                    0 => Form::Bot,
+                    1 => xs.pop().unwrap(),
+                    _ => Form::Xor(xs),
+                };
+                if flipped {
+                    form.negate()
+                } else {
+                    form
+                }
+            }
+
+            Form::Impl(p1, p2) => match (p1.simplify(), p2.simplify()) {
+                (Form::Top, p) => p,
+                (Form::Bot, _) | (_, Form::Top) => Form::Top,
+                (p, Form::Bot) => p.negate(),
+                (p1, p2) if p1 == p2 => Form::Top,
+                (p1, p2) => Form::Impl(Box::new(p1), Box::new(p2)),
+            },
+            Form::Equiv(p1, p2) => match (p1.simplify(), p2.simplify()) {
+                (Form::Top, p) | (p, Form::Top) => p,
+                (Form::Bot, p) | (p, Form::Bot) => p.negate(),
+                (p1, p2) if p1 == p2 => Form::Top,
+                (p1, p2) => Form::Equiv(Box::new(p1), Box::new(p2)),
+            },
+
+            Form::Forall(xs, p) => quantifier(xs, p.simplify(), Form::Forall),
+            Form::Exist(xs, p) => quantifier(xs, p.simplify(), Form::Exist),
+
+            // knowledge is truthful, so knowing a constant is that constant.
+            Form::K(ag, p) => match p.simplify() {
+                p @ (Form::Top | Form::Bot) => p,
+                p => Form::K(ag, Box::new(p)),
+            },
+            // beliefs are consistent, so believing a constant is also that constant.
+            Form::B(ag, p) => match p.simplify() {
+                p @ (Form::Top | Form::Bot) => p,
+                p => Form::B(ag, Box::new(p)),
+            },
+            Form::Kw(ag, p) => match p.simplify() {
+                Form::Top | Form::Bot => Form::Top,
+                p => Form::Kw(ag, Box::new(p)),
+            },
+            Form::CK(ags, p) => match p.simplify() {
+                p @ (Form::Top | Form::Bot) => p,
+                p => Form::CK(ags, Box::new(p)),
+            },
+            Form::DK(ags, p) => match p.simplify() {
+                p @ (Form::Top | Form::Bot) => p,
+                p => Form::DK(ags, Box::new(p)),
+            },
+            Form::CKw(ags, p) => match p.simplify() {
+                Form::Top | Form::Bot => Form::Top,
+                p => Form::CKw(ags, Box::new(p)),
+            },
+            Form::DKw(ags, p) => match p.simplify() {
+                Form::Top | Form::Bot => Form::Top,
+                p => Form::DKw(ags, Box::new(p)),
+            },
+
+            // announcing a tautology changes nothing.
+            Form::PA(p1, p2) => match (p1.simplify(), p2.simplify()) {
+                (Form::Top, p) => p,
+                (_, Form::Top) => Form::Top,
+                (p1, p2) => Form::PA(Box::new(p1), Box::new(p2)),
+            },
+            Form::PAw(p1, p2) => match (p1.simplify(), p2.simplify()) {
+                (Form::Top | Form::Bot, p) => p,
+                (_, p @ (Form::Top | Form::Bot)) => p,
+                (p1, p2) => Form::PAw(Box::new(p1), Box::new(p2)),
+            },
+            Form::GA(ags, p1, p2) => match (p1.simplify(), p2.simplify()) {
+                (Form::Top, p) => p,
+                (_, Form::Top) => Form::Top,
+                (p1, p2) => Form::GA(ags, Box::new(p1), Box::new(p2)),
+            },
+            Form::GAw(ags, p1, p2) => match (p1.simplify(), p2.simplify()) {
+                (Form::Top | Form::Bot, p) => p,
+                (_, p @ (Form::Top | Form::Bot)) => p,
+                (p1, p2) => Form::GAw(ags, Box::new(p1), Box::new(p2)),
+            },
+            Form::Update(action, events, p) => match p.simplify() {
+                Form::Top => Form::Top,
+                _ if events.is_empty() => Form::Top,
+                p => {
+                    let action = ActionModel {
+                        pre: action.pre.into_iter().map(Self::simplify).collect(),
+                        ..*action
+                    };
+                    Form::Update(Box::new(action), events, Box::new(p))
+                }
+            },
+        }
+    }
+}
+
+impl<A, P> Form<A, P> {
+    /// Convert into negation normal form.
+    ///
+    /// Negations are pushed inwards until they apply only to propositions, or to modalities and
+    /// announcements that have no dual in the language. Implications are rewritten as
+    /// disjunctions; equivalences and exclusive disjunctions are kept, absorbing a negation into
+    /// their first operand.
+    pub fn nnf(self) -> Self {
+        match self {
+            Form::Top | Form::Bot | Form::Prop(_) => self,
+            Form::Neg(p) => p.nnf_neg(),
+            Form::Conj(ps) => Form::Conj(ps.into_iter().map(Self::nnf).collect()),
+            Form::Disj(ps) => Form::Disj(ps.into_iter().map(Self::nnf).collect()),
+            Form::Xor(ps) => Form::Xor(ps.into_iter().map(Self::nnf).collect()),
+            Form::Impl(p1, p2) => Form::Disj(vec![p1.nnf_neg(), p2.nnf()]),
+            Form::Equiv(p1, p2) => Form::Equiv(Box::new(p1.nnf()), Box::new(p2.nnf())),
+            Form::Forall(xs, p) => Form::Forall(xs, Box::new(p.nnf())),
+            Form::Exist(xs, p) => Form::Exist(xs, Box::new(p.nnf())),
+            Form::K(ag, p) => Form::K(ag, Box::new(p.nnf())),
+            Form::Kw(ag, p) => Form::Kw(ag, Box::new(p.nnf())),
+            Form::B(ag, p) => Form::B(ag, Box::new(p.nnf())),
+            Form::CK(ags, p) => Form::CK(ags, Box::new(p.nnf())),
+            Form::DK(ags, p) => Form::DK(ags, Box::new(p.nnf())),
+            Form::CKw(ags, p) => Form::CKw(ags, Box::new(p.nnf())),
+            Form::DKw(ags, p) => Form::DKw(ags, Box::new(p.nnf())),
+            Form::PA(p1, p2) => Form::PA(Box::new(p1.nnf()), Box::new(p2.nnf())),
+            Form::PAw(p1, p2) => Form::PAw(Box::new(p1.nnf()), Box::new(p2.nnf())),
+            Form::GA(ags, p1, p2) => Form::GA(ags, Box::new(p1.nnf()), Box::new(p2.nnf())),
+            Form::GAw(ags, p1, p2) => Form::GAw(ags, Box::new(p1.nnf()), Box::new(p2.nnf())),
+            Form::Update(action, events, p) => Form::Update(action, events, Box::new(p.nnf())),
+        }
+    }
+
+    /// Convert the negation of `self` into negation normal form.
+    fn nnf_neg(self) -> Self {
+        match self {
+            Form::Top => Form::Bot,
+            Form::Bot => Form::Top,
+            Form::Prop(_) => Form::Neg(Box::new(self)),
+            Form::Neg(p) => p.nnf(),
+            Form::Conj(ps) => Form::Disj(ps.into_iter().map(Self::nnf_neg).collect()),
+            Form::Disj(ps) => Form::Conj(ps.into_iter().map(Self::nnf_neg).collect()),
+            Form::Xor(ps) => {
+                let mut ps = ps.into_iter();
+                match ps.next() {
+                    None => Form::Top,
+                    Some(p) => {
+                        Form::Xor(iter::once(p.nnf_neg()).chain(ps.map(Self::nnf)).collect())
+                    }
+                }
+            }
+            Form::Impl(p1, p2) => Form::Conj(vec![p1.nnf(), p2.nnf_neg()]),
+            Form::Equiv(p1, p2) => Form::Equiv(Box::new(p1.nnf_neg()), Box::new(p2.nnf())),
+            Form::Forall(xs, p) => Form::Exist(xs, Box::new(p.nnf_neg())),
+            Form::Exist(xs, p) => Form::Forall(xs, Box::new(p.nnf_neg())),
+            // the weak announcements are always executable, so they commute with negation.
+            Form::PAw(p1, p2) => Form::PAw(Box::new(p1.nnf()), Box::new(p2.nnf_neg())),
+            Form::GAw(ags, p1, p2) => Form::GAw(ags, Box::new(p1.nnf()), Box::new(p2.nnf_neg())),
+            p @ (Form::K(..)
+            | Form::Kw(..)
+            | Form::B(..)
+            | Form::CK(..)
+            | Form::DK(..)
+            | Form::CKw(..)
+            | Form::DKw(..)
+            | Form::PA(..)
+            | Form::GA(..)
+            | Form::Update(..)) => Form::Neg(Box::new(p.nnf())),
+        }
+    }
+
+    /// Negate, folding constants and eliminating double negation.
+    fn negate(self) -> Self {
+        match self {
+            Form::Top => Form::Bot,
+            Form::Bot => Form::Top,
+            Form::Neg(p) => *p,
+            p => Form::Neg(Box::new(p)),
+        }
+    }
+}
+
+/// Simplify the children of an associative connective, splicing in those of nested occurrences.
+fn flatten<A, P>(
+    ps: Vec<Form<A, P>>,
+    nested: impl Fn(Form<A, P>) -> Result<Vec<Form<A, P>>, Form<A, P>>,
+) -> Vec<Form<A, P>>
+where
+    A: PartialEq,
+    P: PartialEq,
+{
+    let mut out = Vec::with_capacity(ps.len());
+    for p in ps {
+        match nested(p.simplify()) {
+            Ok(qs) => out.extend(qs),
+            Err(p) => out.push(p),
+        }
+    }
+    out
+}
+
+/// Simplify a conjunction or disjunction, given its unit and its absorbing element.
+fn junction<A, P>(
+    ps: Vec<Form<A, P>>,
+    unit: Form<A, P>,
+    zero: Form<A, P>,
+    nested: impl Fn(Form<A, P>) -> Result<Vec<Form<A, P>>, Form<A, P>>,
+    junct: impl FnOnce(Vec<Form<A, P>>) -> Form<A, P>,
+) -> Form<A, P>
+where
+    A: PartialEq,
+    P: PartialEq,
+{
+    let mut xs: Vec<Form<A, P>> = Vec::new();
+    for p in flatten(ps, nested) {
+        if p == zero {
+            return zero;
+        }
+        if p != unit && !xs.contains(&p) {
+            xs.push(p);
+        }
+    }
+
+    match xs.len() {
+        0 => unit,
+        1 => xs.pop().unwrap(),
+        _ => junct(xs),
+    }
+}
+
+/// Simplify a quantifier, dropping it if it binds nothing or its body is constant.
+fn quantifier<A, P>(
+    xs: Vec<P>,
+    p: Form<A, P>,
+    quant: impl FnOnce(Vec<P>, Box<Form<A, P>>) -> Form<A, P>,
+) -> Form<A, P> {
+    match p {
+        p @ (Form::Top | Form::Bot) => p,
+        p if xs.is_empty() => p,
+        p => quant(xs, Box::new(p)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn p(x: &'static str) -> F {
+        Form::Prop(x)
+    }
+
+    fn neg(p: F) -> F {
+        Form::Neg(Box::new(p))
+    }
+
+    #[test]
+    fn test_simplify() {
+        let form = Form::Conj(vec![
+            p("x"),
+            Form::Top,
+            Form::Conj(vec![p("y"), p("x")]),
+            neg(neg(p("z"))),
+        ]);
+        assert_eq!(form.simplify(), Form::Conj(vec![p("x"), p("y"), p("z")]));
+
+        let form = Form::Disj(vec![p("x"), Form::Conj(vec![Form::Bot, p("y")])]);
+        assert_eq!(form.simplify(), p("x"));
+
+        let form = Form::Xor(vec![Form::Top, p("x"), Form::Xor(vec![Form::Bot])]);
+        assert_eq!(form.simplify(), neg(p("x")));
+
+        let form = Form::GAw(
+            vec!["a"],
+            Box::new(p("x")),
+            Box::new(Form::K("a", Box::new(Form::Top))),
+        );
+        assert_eq!(form.simplify(), Form::Top);
+    }
+
+    #[test]
+    fn test_nnf() {
+        let form = neg(Form::Conj(vec![
+            p("x"),
+            Form::Impl(Box::new(p("y")), Box::new(neg(p("z")))),
+        ]));
+        assert_eq!(
+            form.nnf(),
+            Form::Disj(vec![neg(p("x")), Form::Conj(vec![p("y"), p("z")])])
+        );
+
+        let form = neg(Form::Forall(
+            vec!["x"],
+            Box::new(Form::K("a", Box::new(p("x")))),
+        ));
+        assert_eq!(
+            form.nnf(),
+            Form::Exist(vec!["x"], Box::new(neg(Form::K("a", Box::new(p("x"))))))
+        );
+    }
+}
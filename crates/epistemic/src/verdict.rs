@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use crate::Form;
+
+/// The outcome of checking a formula.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "A: serde::Deserialize<'de>, P: serde::Deserialize<'de> + Ord"))
+)]
+pub enum Verdict<A, P> {
+    /// The formula holds.
+    Holds,
+    /// The formula does not hold, as witnessed by a counterexample.
+    Fails(Counterexample<A, P>),
+}
+
+/// A situation in which a formula is false.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "A: serde::Deserialize<'de>, P: serde::Deserialize<'de> + Ord"))
+)]
+pub struct Counterexample<A, P> {
+    /// The truth value of each proposition at the falsifying world.
+    pub world: BTreeMap<P, bool>,
+    /// The announcements leading to the falsifying world, in the order they were made.
+    pub announcements: Vec<Announced<A, P>>,
+}
+
+/// An announcement made on the way to a counterexample.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Announced<A, P> {
+    /// The agents that received the announcement, or `None` if it was public.
+    pub group: Option<Vec<A>>,
+    /// The announced formula.
+    pub form: Form<A, P>,
+    /// The truth value of the announced formula, i.e. what the agents learned.
+    pub truth: bool,
+}
+
+impl<A, P> Verdict<A, P> {
+    /// Check if the formula holds.
+    pub fn holds(&self) -> bool {
+        matches!(self, Verdict::Holds)
+    }
+
+    /// Get the counterexample, if the formula does not hold.
+    pub fn counterexample(&self) -> Option<&Counterexample<A, P>> {
+        match self {
+            Verdict::Holds => None,
+            Verdict::Fails(cex) => Some(cex),
+        }
+    }
+}
+
+impl<A, P> Counterexample<A, P> {
+    /// Create a counterexample at `world`, reached without any announcements.
+    pub fn new(world: BTreeMap<P, bool>) -> Self {
+        Self {
+            world,
+            announcements: Vec::new(),
+        }
+    }
+}
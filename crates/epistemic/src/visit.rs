@@ -0,0 +1,349 @@
+use std::convert::Infallible;
+
+use crate::{ActionModel, Form};
+
+/// A traversal of a [`Form`] by reference.
+///
+/// Implementors override only the cases they care about, calling [`walk_form`] from
+/// [`Visitor::visit_form`] to continue into the children.
+pub trait Visitor<'f, A, P> {
+    fn visit_form(&mut self, form: &'f Form<A, P>) {
+        walk_form(self, form)
+    }
+
+    /// Visit an agent, either on its own or in a group.
+    fn visit_agent(&mut self, _ag: &'f A) {}
+
+    /// Visit a proposition occurring in a formula.
+    fn visit_prop(&mut self, _p: &'f P) {}
+
+    /// Visit the propositions bound by a quantifier.
+    fn visit_binder(&mut self, _xs: &'f [P]) {}
+}
+
+/// Visit the agents, propositions and subformulas of `form`.
+pub fn walk_form<'f, V, A, P>(visitor: &mut V, form: &'f Form<A, P>)
+where
+    V: Visitor<'f, A, P> + ?Sized,
+{
+    match form {
+        Form::Top | Form::Bot => {}
+        Form::Prop(p) => visitor.visit_prop(p),
+        Form::Neg(p) => visitor.visit_form(p),
+        Form::Conj(ps) | Form::Disj(ps) | Form::Xor(ps) => {
+            ps.iter().for_each(|p| visitor.visit_form(p))
+        }
+        Form::Impl(p1, p2) | Form::Equiv(p1, p2) | Form::PA(p1, p2) | Form::PAw(p1, p2) => {
+            visitor.visit_form(p1);
+            visitor.visit_form(p2);
+        }
+        Form::Forall(xs, p) | Form::Exist(xs, p) => {
+            visitor.visit_binder(xs);
+            visitor.visit_form(p);
+        }
+        Form::K(ag, p) | Form::Kw(ag, p) | Form::B(ag, p) => {
+            visitor.visit_agent(ag);
+            visitor.visit_form(p);
+        }
+        Form::CK(ags, p) | Form::DK(ags, p) | Form::CKw(ags, p) | Form::DKw(ags, p) => {
+            ags.iter().for_each(|ag| visitor.visit_agent(ag));
+            visitor.visit_form(p);
+        }
+        Form::GA(ags, p1, p2) | Form::GAw(ags, p1, p2) => {
+            ags.iter().for_each(|ag| visitor.visit_agent(ag));
+            visitor.visit_form(p1);
+            visitor.visit_form(p2);
+        }
+        Form::Update(action, _, p) => {
+            action.agents().for_each(|ag| visitor.visit_agent(ag));
+            action.pre.iter().for_each(|pre| visitor.visit_form(pre));
+            visitor.visit_form(p);
+        }
+    }
+}
+
+/// A rewriting of a [`Form`] by value, possibly changing the types of agents and propositions.
+///
+/// Override [`Fold::fold_form`] to rewrite subformulas, calling [`fold_form`] to continue into
+/// the children.
+pub trait Fold<A, P> {
+    type Agent;
+    type Prop;
+    type Error;
+
+    fn fold_form(
+        &mut self,
+        form: Form<A, P>,
+    ) -> Result<Form<Self::Agent, Self::Prop>, Self::Error> {
+        fold_form(self, form)
+    }
+
+    fn fold_agent(&mut self, ag: A) -> Result<Self::Agent, Self::Error>;
+
+    /// Fold a proposition, either occurring in a formula or bound by a quantifier.
+    fn fold_prop(&mut self, p: P) -> Result<Self::Prop, Self::Error>;
+}
+
+/// Fold the agents, propositions and subformulas of `form`.
+pub fn fold_form<F, A, P>(
+    folder: &mut F,
+    form: Form<A, P>,
+) -> Result<Form<F::Agent, F::Prop>, F::Error>
+where
+    F: Fold<A, P> + ?Sized,
+{
+    let form = match form {
+        Form::Top => Form::Top,
+        Form::Bot => Form::Bot,
+        Form::Prop(p) => Form::Prop(folder.fold_prop(p)?),
+        Form::Neg(p) => Form::Neg(fold_box(folder, *p)?),
+        Form::Conj(ps) => Form::Conj(fold_vec(folder, ps)?),
+        Form::Disj(ps) => Form::Disj(fold_vec(folder, ps)?),
+        Form::Xor(ps) => Form::Xor(fold_vec(folder, ps)?),
+        Form::Impl(p1, p2) => Form::Impl(fold_box(folder, *p1)?, fold_box(folder, *p2)?),
+        Form::Equiv(p1, p2) => Form::Equiv(fold_box(folder, *p1)?, fold_box(folder, *p2)?),
+        Form::Forall(xs, p) => Form::Forall(fold_props(folder, xs)?, fold_box(folder, *p)?),
+        Form::Exist(xs, p) => Form::Exist(fold_props(folder, xs)?, fold_box(folder, *p)?),
+        Form::K(ag, p) => Form::K(folder.fold_agent(ag)?, fold_box(folder, *p)?),
+        Form::Kw(ag, p) => Form::Kw(folder.fold_agent(ag)?, fold_box(folder, *p)?),
+        Form::B(ag, p) => Form::B(folder.fold_agent(ag)?, fold_box(folder, *p)?),
+        Form::CK(ags, p) => Form::CK(fold_agents(folder, ags)?, fold_box(folder, *p)?),
+        Form::DK(ags, p) => Form::DK(fold_agents(folder, ags)?, fold_box(folder, *p)?),
+        Form::CKw(ags, p) => Form::CKw(fold_agents(folder, ags)?, fold_box(folder, *p)?),
+        Form::DKw(ags, p) => Form::DKw(fold_agents(folder, ags)?, fold_box(folder, *p)?),
+        Form::PA(p1, p2) => Form::PA(fold_box(folder, *p1)?, fold_box(folder, *p2)?),
+        Form::PAw(p1, p2) => Form::PAw(fold_box(folder, *p1)?, fold_box(folder, *p2)?),
+        Form::GA(ags, p1, p2) => Form::GA(
+            fold_agents(folder, ags)?,
+            fold_box(folder, *p1)?,
+            fold_box(folder, *p2)?,
+        ),
+        Form::GAw(ags, p1, p2) => Form::GAw(
+            fold_agents(folder, ags)?,
+            fold_box(folder, *p1)?,
+            fold_box(folder, *p2)?,
+        ),
+        Form::Update(action, events, p) => Form::Update(
+            Box::new(fold_action(folder, *action)?),
+            events,
+            fold_box(folder, *p)?,
+        ),
+    };
+    Ok(form)
+}
+
+type Folded<F, A, P> = Form<<F as Fold<A, P>>::Agent, <F as Fold<A, P>>::Prop>;
+
+fn fold_box<F, A, P>(folder: &mut F, p: Form<A, P>) -> Result<Box<Folded<F, A, P>>, F::Error>
+where
+    F: Fold<A, P> + ?Sized,
+{
+    folder.fold_form(p).map(Box::new)
+}
+
+fn fold_vec<F, A, P>(folder: &mut F, ps: Vec<Form<A, P>>) -> Result<Vec<Folded<F, A, P>>, F::Error>
+where
+    F: Fold<A, P> + ?Sized,
+{
+    ps.into_iter().map(|p| folder.fold_form(p)).collect()
+}
+
+fn fold_agents<F, A, P>(folder: &mut F, ags: Vec<A>) -> Result<Vec<F::Agent>, F::Error>
+where
+    F: Fold<A, P> + ?Sized,
+{
+    ags.into_iter().map(|ag| folder.fold_agent(ag)).collect()
+}
+
+fn fold_props<F, A, P>(folder: &mut F, xs: Vec<P>) -> Result<Vec<F::Prop>, F::Error>
+where
+    F: Fold<A, P> + ?Sized,
+{
+    xs.into_iter().map(|x| folder.fold_prop(x)).collect()
+}
+
+fn fold_action<F, A, P>(
+    folder: &mut F,
+    action: ActionModel<A, P>,
+) -> Result<ActionModel<F::Agent, F::Prop>, F::Error>
+where
+    F: Fold<A, P> + ?Sized,
+{
+    let relations = action
+        .relations
+        .into_iter()
+        .map(|(ag, classes)| Ok((folder.fold_agent(ag)?, classes)))
+        .collect::<Result<_, _>>()?;
+    Ok(ActionModel {
+        pre: fold_vec(folder, action.pre)?,
+        relations,
+    })
+}
+
+/// A [`Fold`] applying a function to each agent and to each proposition.
+struct MapWith<F, G>(F, G);
+
+impl<A, P, B, Q, E, F, G> Fold<A, P> for MapWith<F, G>
+where
+    F: FnMut(A) -> Result<B, E>,
+    G: FnMut(P) -> Result<Q, E>,
+{
+    type Agent = B;
+    type Prop = Q;
+    type Error = E;
+
+    fn fold_agent(&mut self, ag: A) -> Result<B, E> {
+        (self.0)(ag)
+    }
+
+    fn fold_prop(&mut self, p: P) -> Result<Q, E> {
+        (self.1)(p)
+    }
+}
+
+impl<A, P> Form<A, P> {
+    /// Apply fallible functions to each agent and each proposition, stopping at the first error.
+    pub fn try_map<B, Q, E>(
+        self,
+        agent: impl FnMut(A) -> Result<B, E>,
+        prop: impl FnMut(P) -> Result<Q, E>,
+    ) -> Result<Form<B, Q>, E> {
+        MapWith(agent, prop).fold_form(self)
+    }
+
+    /// Apply functions to each agent and each proposition.
+    pub fn map<B, Q>(
+        self,
+        mut agent: impl FnMut(A) -> B,
+        mut prop: impl FnMut(P) -> Q,
+    ) -> Form<B, Q> {
+        let result: Result<_, Infallible> = self.try_map(|ag| Ok(agent(ag)), |p| Ok(prop(p)));
+        result.unwrap_or_else(|never| match never {})
+    }
+
+    /// Apply a function to each agent.
+    pub fn map_agents<B>(self, agent: impl FnMut(A) -> B) -> Form<B, P> {
+        self.map(agent, |p| p)
+    }
+
+    /// Apply a function to each proposition, including those bound by quantifiers.
+    pub fn map_props<Q>(self, prop: impl FnMut(P) -> Q) -> Form<A, Q> {
+        self.map(|ag| ag, prop)
+    }
+
+    /// Borrow the agents and propositions.
+    pub fn as_ref<'a>(&'a self) -> Form<&'a A, &'a P> {
+        let boxed = |p: &'a Self| Box::new(p.as_ref());
+        let vec = |ps: &'a [Self]| ps.iter().map(Self::as_ref).collect();
+        match self {
+            Form::Top => Form::Top,
+            Form::Bot => Form::Bot,
+            Form::Prop(p) => Form::Prop(p),
+            Form::Neg(p) => Form::Neg(boxed(p)),
+            Form::Conj(ps) => Form::Conj(vec(ps)),
+            Form::Disj(ps) => Form::Disj(vec(ps)),
+            Form::Xor(ps) => Form::Xor(vec(ps)),
+            Form::Impl(p1, p2) => Form::Impl(boxed(p1), boxed(p2)),
+            Form::Equiv(p1, p2) => Form::Equiv(boxed(p1), boxed(p2)),
+            Form::Forall(xs, p) => Form::Forall(xs.iter().collect(), boxed(p)),
+            Form::Exist(xs, p) => Form::Exist(xs.iter().collect(), boxed(p)),
+            Form::K(ag, p) => Form::K(ag, boxed(p)),
+            Form::Kw(ag, p) => Form::Kw(ag, boxed(p)),
+            Form::B(ag, p) => Form::B(ag, boxed(p)),
+            Form::CK(ags, p) => Form::CK(ags.iter().collect(), boxed(p)),
+            Form::DK(ags, p) => Form::DK(ags.iter().collect(), boxed(p)),
+            Form::CKw(ags, p) => Form::CKw(ags.iter().collect(), boxed(p)),
+            Form::DKw(ags, p) => Form::DKw(ags.iter().collect(), boxed(p)),
+            Form::PA(p1, p2) => Form::PA(boxed(p1), boxed(p2)),
+            Form::PAw(p1, p2) => Form::PAw(boxed(p1), boxed(p2)),
+            Form::GA(ags, p1, p2) => Form::GA(ags.iter().collect(), boxed(p1), boxed(p2)),
+            Form::GAw(ags, p1, p2) => Form::GAw(ags.iter().collect(), boxed(p1), boxed(p2)),
+            Form::Update(action, events, p) => Form::Update(
+                Box::new(ActionModel::as_ref(action)),
+                events.clone(),
+                boxed(p),
+            ),
+        }
+    }
+
+    /// Iterate over the propositions.
+    pub fn vocab<'a>(&'a self) -> Box<dyn Iterator<Item = &'a P> + 'a> {
+        struct Vocab<'f, P>(Vec<&'f P>);
+
+        impl<'f, A, P> Visitor<'f, A, P> for Vocab<'f, P> {
+            fn visit_prop(&mut self, p: &'f P) {
+                self.0.push(p);
+            }
+        }
+
+        let mut vocab = Vocab(Vec::new());
+        vocab.visit_form(self);
+        Box::new(vocab.0.into_iter())
+    }
+
+    /// Iterate over the propositions bound by quantifiers, which may not occur in their bodies.
+    pub fn binders<'a>(&'a self) -> Box<dyn Iterator<Item = &'a P> + 'a> {
+        struct Binders<'f, P>(Vec<&'f P>);
+
+        impl<'f, A, P> Visitor<'f, A, P> for Binders<'f, P> {
+            fn visit_binder(&mut self, xs: &'f [P]) {
+                self.0.extend(xs);
+            }
+        }
+
+        let mut binders = Binders(Vec::new());
+        binders.visit_form(self);
+        Box::new(binders.0.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type F = Form<&'static str, &'static str>;
+
+    fn p(x: &'static str) -> F {
+        Form::Prop(x)
+    }
+
+    #[test]
+    fn test_map() {
+        let form = Form::Disj(vec![
+            Form::K("a", Box::new(p("x"))),
+            Form::Forall(vec!["y"], Box::new(p("y"))),
+        ]);
+
+        let renamed = form.clone().map(str::to_uppercase, |x| format!("{x}'"));
+        assert_eq!(
+            renamed,
+            Form::Disj(vec![
+                Form::K("A".to_string(), Box::new(Form::Prop("x'".to_string()))),
+                Form::Forall(
+                    vec!["y'".to_string()],
+                    Box::new(Form::Prop("y'".to_string()))
+                ),
+            ])
+        );
+
+        let resolved = form
+            .clone()
+            .try_map(Ok, |x| if x == "x" { Ok(0) } else { Err(x) });
+        assert_eq!(resolved, Err("y"));
+
+        assert_eq!(Form::from(&form), form.as_ref());
+        assert!(matches!(form.as_ref(), Form::Disj(_)));
+    }
+
+    #[test]
+    fn test_vocab() {
+        let form = Form::Impl(
+            Box::new(Form::Top),
+            Box::new(Form::Conj(vec![p("x"), p("y")])),
+        );
+        assert_eq!(form.vocab().collect::<Vec<_>>(), vec![&"x", &"y"]);
+
+        let form = Form::Exist(vec!["z"], Box::new(form));
+        assert_eq!(form.binders().collect::<Vec<_>>(), vec![&"z"]);
+    }
+}
@@ -1,4 +1,4 @@
-use hermit_syntax::attribute::{agent, ensure, forget, have, ItemAttribute};
+use hermit_syntax::attribute::{agent, ensure, forget, group, have, observers, ItemAttribute};
 
 macro_rules! attribute {
     ($name:ident) => {
@@ -16,3 +16,5 @@ attribute!(agent);
 attribute!(have);
 attribute!(ensure);
 attribute!(forget);
+attribute!(group);
+attribute!(observers);
@@ -2,4 +2,7 @@ pub mod semantics;
 pub mod syntax;
 
 pub use semantics::{AnnouncementFlow, Network, Semantics};
-pub use syntax::{TypedMeta, UntypedForget, UntypedForm, UntypedMeta, UntypedRef};
+pub use syntax::{
+    AgentSet, Environment, Principal, TypedMeta, UntypedForget, UntypedForm, UntypedMeta,
+    UntypedRef,
+};
@@ -0,0 +1,158 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::semantics::{Flow, Verdict};
+use crate::syntax::{TypedForm, TypedMeta};
+
+/// A checker for knowledge structures, compiled in by the cargo feature of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+    /// Binary decision diagrams, in `epistemic-bdd`.
+    Bdd,
+    /// Explicit Kripke models, in `epistemic-kripke`.
+    Kripke,
+    /// The standalone `smcdel` executable.
+    Smcdel,
+    /// SMCDEL linked in through the Haskell bridge.
+    SmcdelFfi,
+}
+
+/// An error selecting or running a [`Backend`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BackendError {
+    #[error("unknown backend `{0}`, expected one of `bdd`, `kripke`, `smcdel` or `smcdel-ffi`")]
+    Unknown(String),
+    #[error("backend `{0}` was not compiled in, rebuild with the `{0}` feature")]
+    NotCompiled(Backend),
+    #[error("backend `{0}` failed: {1}")]
+    Failed(Backend, String),
+}
+
+impl Backend {
+    /// Every backend, whether compiled in or not.
+    pub const ALL: [Self; 4] = [Self::Bdd, Self::Kripke, Self::Smcdel, Self::SmcdelFfi];
+
+    /// The name of the backend, which is also the name of its cargo feature.
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Bdd => "bdd",
+            Backend::Kripke => "kripke",
+            Backend::Smcdel => "smcdel",
+            Backend::SmcdelFfi => "smcdel-ffi",
+        }
+    }
+
+    /// Check if the backend was compiled in.
+    pub fn is_compiled(self) -> bool {
+        match self {
+            Backend::Bdd => cfg!(feature = "bdd"),
+            Backend::Kripke => cfg!(feature = "kripke"),
+            Backend::Smcdel => cfg!(feature = "smcdel"),
+            Backend::SmcdelFfi => cfg!(feature = "smcdel-ffi"),
+        }
+    }
+
+    /// Iterate over the backends that were compiled in.
+    pub fn compiled() -> impl Iterator<Item = Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|backend| backend.is_compiled())
+    }
+
+    /// Parse the name of a backend that was compiled in.
+    pub fn select(name: &str) -> Result<Self, BackendError> {
+        let backend: Self = name.parse()?;
+        if !backend.is_compiled() {
+            return Err(BackendError::NotCompiled(backend));
+        }
+        Ok(backend)
+    }
+
+    /// Check `form` on the knowledge structure of `meta`, after the announcements of `flow`.
+    pub fn check<'i, A, L, F>(
+        self,
+        meta: &'i TypedMeta<A, L>,
+        flow: F,
+        form: TypedForm<&'i A, &'i L>,
+    ) -> Result<Verdict<&'i A, &'i L>, BackendError>
+    where
+        A: Ord,
+        L: Ord,
+        F: Flow<Location = &'i L>,
+    {
+        #[allow(unused_imports)]
+        use crate::semantics::Semantics;
+
+        match self {
+            #[cfg(feature = "bdd")]
+            Backend::Bdd => {
+                let sem = meta.semantics::<F, epistemic_bdd::BddKnowStruct<_, _>>(flow);
+                Ok(sem.check(form))
+            }
+            #[cfg(feature = "kripke")]
+            Backend::Kripke => {
+                let sem = meta.semantics::<F, epistemic_kripke::KripkeModel<_, _>>(flow);
+                Ok(sem.check(form))
+            }
+            #[cfg(feature = "smcdel")]
+            Backend::Smcdel => {
+                let sem = meta.semantics::<F, epistemic_smcdel::SmcdelProcess<_, _>>(flow);
+                let form = sem.announced(form);
+                sem.know()
+                    .try_check(form)
+                    .map_err(|err| BackendError::Failed(self, err.to_string()))
+            }
+            #[cfg(feature = "smcdel-ffi")]
+            Backend::SmcdelFfi => {
+                let sem = meta.semantics::<F, epistemic_smcdel::SMCDEL<_, _>>(flow);
+                let form = sem.announced(form);
+                sem.know()
+                    .try_check(form)
+                    .map_err(|err| BackendError::Failed(self, err.to_string()))
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = (meta, flow, form);
+                Err(BackendError::NotCompiled(self))
+            }
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = BackendError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.name() == s)
+            .ok_or_else(|| BackendError::Unknown(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_select() {
+        for backend in Backend::ALL {
+            assert_eq!(backend.name().parse(), Ok(backend));
+        }
+        assert_eq!(
+            Backend::select("z3"),
+            Err(BackendError::Unknown("z3".to_string()))
+        );
+        assert_eq!(
+            Backend::select("smcdel-ffi").is_ok(),
+            Backend::SmcdelFfi.is_compiled()
+        );
+    }
+}
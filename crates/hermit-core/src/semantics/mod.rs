@@ -1,6 +1,8 @@
+mod backend;
 mod network;
 
 pub mod flow;
 
+pub use backend::{Backend, BackendError};
 pub use flow::Flow;
 pub use network::*;
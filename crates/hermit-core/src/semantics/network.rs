@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use super::flow::{Flow, Transitive};
 
-pub use epistemic::{Form, KnowStruct, Semantics};
+pub use epistemic::{ActionModel, Counterexample, Form, KnowStruct, Semantics, Verdict};
 
 /// A group of agents.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
@@ -16,6 +16,9 @@ pub struct Group<A> {
 pub struct Channel<A> {
     /// The group of agents listening at the channel.
     pub listeners: Group<A>,
+    /// The agents that see when something is announced at the channel, but not what, or `None`
+    /// if every agent does.
+    pub observers: Option<Group<A>>,
 }
 
 /// A collection of channels.
@@ -30,6 +33,8 @@ pub struct Network<A, L> {
 pub struct Announcement<A, V> {
     /// The receivers of the announcement.
     pub target: Group<A>,
+    /// The agents that see that the announcement is made, or `None` if every agent does.
+    pub observers: Option<Group<A>>,
     /// The content of the announcement.
     pub val: V,
 }
@@ -54,7 +59,18 @@ where
 impl<A> Channel<A> {
     /// Create a new channel with the given `listeners`.
     pub fn new(listeners: Group<A>) -> Self {
-        Self { listeners }
+        Self {
+            listeners,
+            observers: None,
+        }
+    }
+
+    /// Let only `observers`, besides the listeners, see when something is announced.
+    pub fn with_observers(self, observers: Group<A>) -> Self {
+        Self {
+            observers: Some(observers),
+            ..self
+        }
     }
 }
 
@@ -64,7 +80,11 @@ where
 {
     /// Create an announcement of `val` through the channel to all its listeners.
     pub fn announcement<V>(&self, val: V) -> Announcement<A, V> {
-        Announcement::new(self.listeners.clone(), val)
+        Announcement {
+            target: self.listeners.clone(),
+            observers: self.observers.clone(),
+            val,
+        }
     }
 }
 
@@ -86,7 +106,41 @@ where
 impl<A, V> Announcement<A, V> {
     /// Create a new announcement of `val` to `target`.
     pub fn new(target: Group<A>, val: V) -> Self {
-        Self { target, val }
+        Self {
+            target,
+            observers: None,
+            val,
+        }
+    }
+}
+
+impl<A, L> Announcement<A, L>
+where
+    A: Clone + PartialEq,
+    L: Clone,
+{
+    /// The formula that `form` holds after the announcement.
+    ///
+    /// When every agent sees that the announcement is made, it is a semi-private group
+    /// announcement. Otherwise, it is an action model in which the agents that do not cannot rule
+    /// out that nothing happened.
+    fn then(self, form: Form<A, L>) -> Form<A, L> {
+        let ags: Vec<_> = self.target.members.into_iter().collect();
+        let val = Form::Prop(self.val);
+        let Some(observers) = self.observers else {
+            return Form::GAw(ags, Box::new(val), Box::new(form));
+        };
+
+        // the events announce the value, its negation, or nothing at all.
+        let neg = Form::Neg(Box::new(val.clone()));
+        let action = ActionModel::new(vec![val, neg, Form::Top]);
+        let action = observers.members.into_iter().fold(action, |action, ag| {
+            action.with_partition(ag, vec![vec![0, 1], vec![2]])
+        });
+        let action = ags.into_iter().fold(action, |action, ag| {
+            action.with_partition(ag, vec![vec![0], vec![1], vec![2]])
+        });
+        Form::Update(Box::new(action), vec![0, 1], Box::new(form))
     }
 }
 
@@ -168,28 +222,92 @@ where
     pub fn new(flow: F, know: K) -> Self {
         Self { flow, know }
     }
+
+    /// The knowledge structure on which formulas are checked.
+    pub fn know(&self) -> &K {
+        &self.know
+    }
+}
+
+impl<F, K> AnnouncementFlowSat<F, K>
+where
+    F: AnnouncementFlow,
+    K: KnowStruct<Agent = F::Agent, Prop = F::Location>,
+    F::Agent: Clone + PartialEq,
+    F::Location: Ord + Copy,
+{
+    /// The formula checked on the knowledge structure for `form`, which holds after all the
+    /// announcements of its propositions.
+    pub fn announced(&self, form: Form<F::Agent, F::Location>) -> Form<F::Agent, F::Location> {
+        let form = form.simplify();
+        let vocab: BTreeSet<_> = form.vocab().copied().collect();
+        let anns: Vec<_> = vocab
+            .into_iter()
+            .flat_map(|b| self.flow.announcements(b))
+            .collect();
+        anns.into_iter().fold(form, |form, ann| ann.then(form))
+    }
 }
 
 impl<F, K> Semantics for AnnouncementFlowSat<F, K>
 where
     F: AnnouncementFlow,
     K: KnowStruct<Agent = F::Agent, Prop = F::Location>,
-    F::Agent: Clone,
+    F::Agent: Clone + PartialEq,
     F::Location: Ord + Copy,
 {
     type Agent = F::Agent;
     type Prop = F::Location;
 
-    fn sat(&self, form: Form<Self::Agent, Self::Prop>) -> bool {
-        let anns: Vec<_> = form
-            .vocab()
-            .flat_map(|b| self.flow.announcements(*b))
-            .collect();
-        let form = anns.into_iter().fold(form, |form, ann| {
-            let ags = ann.target.members.into_iter().collect();
-            Form::GAw(ags, Box::new(Form::Prop(ann.val)), Box::new(form))
-        });
+    /// Check `form` after all the announcements of its propositions.
+    ///
+    /// The counterexample, if any, includes the announcements that lead to the falsifying world.
+    fn check(&self, form: Form<Self::Agent, Self::Prop>) -> Verdict<Self::Agent, Self::Prop> {
+        self.know.check(self.announced(form))
+    }
+}
+
+#[cfg(all(test, feature = "bdd"))]
+mod test {
+    use epistemic_bdd::BddKnowStruct;
+
+    use super::*;
+
+    /// Each location sends its data to the locations it maps to.
+    impl Flow for BTreeMap<&'static str, Vec<&'static str>> {
+        type Location = &'static str;
+
+        fn forward(&self, loc: Self::Location) -> impl Iterator<Item = Self::Location> {
+            self.get(loc).into_iter().flatten().copied()
+        }
+    }
+
+    #[test]
+    fn test_observers() {
+        // `x` is sent to itself, over a channel to `a` that `b` watches.
+        let chan = Channel::new(Group::from_iter(["a"]));
+        let sat = |chan: Channel<&'static str>| {
+            let network = Network::new(BTreeMap::from([("x", chan)]));
+            let flow = NetworkFlow::new(network, BTreeMap::from([("x", vec!["x"])]));
+            let know: BddKnowStruct<_, _> = KnowStruct::new(
+                vec!["x"],
+                Form::Top,
+                vec![("a", vec![]), ("b", vec![]), ("c", vec![])],
+            );
+            AnnouncementFlowSat::new(flow, know)
+        };
+        let kw = |ag, p| Form::Kw(ag, Box::new(p));
+        let know = |ag, p| Form::K(ag, Box::new(p));
+        let learnt = || kw("a", Form::Prop("x"));
+
+        // the listener learns the content, and the observer only that it was sent.
+        let watched = sat(chan.clone().with_observers(Group::from_iter(["b"])));
+        assert!(watched.sat(learnt()));
+        assert!(watched.sat(Form::Neg(Box::new(kw("b", Form::Prop("x"))))));
+        assert!(watched.sat(know("b", learnt())));
+        assert!(watched.sat(Form::Neg(Box::new(know("c", learnt())))));
 
-        self.know.sat(form)
+        // without observers, every agent sees that it was sent.
+        assert!(sat(chan).sat(know("c", learnt())));
     }
 }
@@ -0,0 +1,159 @@
+use std::fmt::{self, Display, Formatter};
+
+/// An agent that owns locations.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Principal<A> {
+    /// An agent declared by `#[agent]`.
+    Named(A),
+    /// The anonymous agent of functions without an `#[agent]` annotation.
+    Default,
+    /// The hidden agent of functions known to be pure, which no `agents` quantifier ranges over.
+    Pure,
+    /// A built-in agent standing for the outside world.
+    Env(Environment),
+}
+
+/// The parts of the outside world that programs announce data to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Environment {
+    /// Other hosts, through sockets.
+    Network,
+    /// Files on disk.
+    Filesystem,
+    /// The standard output and error streams.
+    Stdout,
+    /// Child processes and the environment variables they inherit.
+    Env,
+}
+
+impl<A> Principal<A> {
+    /// Check if an `agents` quantifier ranges over the agent, given whether it includes the
+    /// default agent.
+    pub fn is_quantified(&self, default: bool) -> bool {
+        match self {
+            Principal::Named(_) | Principal::Env(_) => true,
+            Principal::Default => default,
+            Principal::Pure => false,
+        }
+    }
+
+    /// The name of the agent, unless it is anonymous.
+    pub fn name(&self) -> Option<&A> {
+        match self {
+            Principal::Named(ag) => Some(ag),
+            Principal::Default | Principal::Pure | Principal::Env(_) => None,
+        }
+    }
+}
+
+impl<A> From<A> for Principal<A> {
+    fn from(ag: A) -> Self {
+        Principal::Named(ag)
+    }
+}
+
+impl<A> Display for Principal<A>
+where
+    A: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Principal::Named(ag) => ag.fmt(f),
+            Principal::Default => write!(f, "<default>"),
+            Principal::Pure => write!(f, "<pure>"),
+            Principal::Env(env) => env.fmt(f),
+        }
+    }
+}
+
+impl Environment {
+    pub const ALL: [Self; 4] = [
+        Environment::Network,
+        Environment::Filesystem,
+        Environment::Stdout,
+        Environment::Env,
+    ];
+
+    /// The name by which formulas refer to the agent.
+    pub fn name(self) -> &'static str {
+        match self {
+            Environment::Network => "network",
+            Environment::Filesystem => "filesystem",
+            Environment::Stdout => "stdout",
+            Environment::Env => "env",
+        }
+    }
+
+    /// The agent of the given name.
+    pub fn named(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|env| env.name() == name)
+    }
+
+    /// The agent that a call to the function at `path` announces its arguments to, if it is a
+    /// known sink of the standard library.
+    ///
+    /// Paths are as printed by the compiler, with trait methods qualified by their impl, such as
+    /// `<std::net::TcpStream as std::io::Write>::write`.
+    pub fn of_sink(path: &str) -> Option<Self> {
+        SINKS
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix))
+            .map(|&(_, env)| env)
+    }
+}
+
+/// The path prefixes of the functions that announce their arguments to the outside world.
+const SINKS: &[(&str, Environment)] = &[
+    ("std::net::TcpStream::connect", Environment::Network),
+    ("std::net::UdpSocket::send", Environment::Network),
+    (
+        "<std::net::TcpStream as std::io::Write>::",
+        Environment::Network,
+    ),
+    (
+        "<&std::net::TcpStream as std::io::Write>::",
+        Environment::Network,
+    ),
+    ("std::fs::write", Environment::Filesystem),
+    ("std::fs::File::create", Environment::Filesystem),
+    ("std::fs::OpenOptions::open", Environment::Filesystem),
+    (
+        "<std::fs::File as std::io::Write>::",
+        Environment::Filesystem,
+    ),
+    (
+        "<&std::fs::File as std::io::Write>::",
+        Environment::Filesystem,
+    ),
+    ("std::io::_print", Environment::Stdout),
+    ("std::io::_eprint", Environment::Stdout),
+    ("<std::io::Stdout as std::io::Write>::", Environment::Stdout),
+    ("<std::io::Stderr as std::io::Write>::", Environment::Stdout),
+    ("std::process::Command::", Environment::Env),
+    ("std::env::set_var", Environment::Env),
+];
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_of_sink() {
+        let sink = Environment::of_sink;
+        assert_eq!(sink("std::fs::write"), Some(Environment::Filesystem));
+        assert_eq!(
+            sink("<std::net::TcpStream as std::io::Write>::write_all"),
+            Some(Environment::Network)
+        );
+        assert_eq!(sink("std::process::Command::spawn"), Some(Environment::Env));
+        assert_eq!(sink("std::fs::read"), None);
+    }
+}
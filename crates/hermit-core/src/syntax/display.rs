@@ -0,0 +1,157 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{AgentSet, UntypedForm, UntypedRef};
+
+/// Format in the surface syntax, such that parsing gives back the same formula.
+impl<A, L> Display for UntypedForm<A, L>
+where
+    A: Display,
+    L: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_surface(f, Ctx::TOP)
+    }
+}
+
+impl<A> Display for UntypedRef<A>
+where
+    A: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The binding powers of the infix operators, as in the parser.
+const IMPL: (u8, u8) = (2, 1);
+const BIIMPL: (u8, u8) = (3, 4);
+const JUNCT: (u8, u8) = (5, 6);
+
+/// The operators surrounding a subformula.
+#[derive(Debug, Clone, Copy)]
+struct Ctx {
+    /// The right binding power of the preceding infix operator.
+    before: u8,
+    /// The left binding power of the following infix operator, if any.
+    after: Option<u8>,
+}
+
+impl Ctx {
+    const TOP: Ctx = Ctx {
+        before: 0,
+        after: None,
+    };
+}
+
+impl<A, L> UntypedForm<A, L>
+where
+    A: Display,
+    L: Display,
+{
+    fn fmt_surface(&self, f: &mut Formatter<'_>, ctx: Ctx) -> fmt::Result {
+        let infix = match self {
+            UntypedForm::Conj(p1, p2) => Some((p1, "&&", JUNCT, p2)),
+            UntypedForm::Disj(p1, p2) => Some((p1, "||", JUNCT, p2)),
+            UntypedForm::Xor(p1, p2) => Some((p1, "^", JUNCT, p2)),
+            UntypedForm::Impl(p1, p2) => Some((p1, "->", IMPL, p2)),
+            UntypedForm::BiImpl(p1, p2) => Some((p1, "==", BIIMPL, p2)),
+            _ => None,
+        };
+
+        // infix operators bind weaker than their surroundings, and prefix operators extend as far
+        // right as possible.
+        let parens = match infix {
+            Some((_, _, (lbp, rbp), _)) => {
+                lbp < ctx.before || ctx.after.is_some_and(|after| after >= rbp)
+            }
+            None => {
+                matches!(
+                    self,
+                    UntypedForm::Neg(_)
+                        | UntypedForm::Forall(..)
+                        | UntypedForm::Exist(..)
+                        | UntypedForm::ForG(..)
+                ) && ctx.after.is_some()
+            }
+        };
+        if parens {
+            write!(f, "(")?;
+            self.fmt_surface(f, Ctx::TOP)?;
+            return write!(f, ")");
+        }
+
+        if let Some((p1, op, (lbp, rbp), p2)) = infix {
+            let lhs = Ctx {
+                before: ctx.before,
+                after: Some(lbp),
+            };
+            let rhs = Ctx {
+                before: rbp,
+                after: ctx.after,
+            };
+            p1.fmt_surface(f, lhs)?;
+            write!(f, " {op} ")?;
+            return p2.fmt_surface(f, rhs);
+        }
+
+        let operand = Ctx {
+            before: 0,
+            after: ctx.after,
+        };
+        match self {
+            UntypedForm::Top => write!(f, "true"),
+            UntypedForm::Bot => write!(f, "false"),
+            UntypedForm::Prop(x) => write!(f, "{x}"),
+            UntypedForm::Neg(p) => {
+                write!(f, "!")?;
+                p.fmt_surface(f, operand)
+            }
+            UntypedForm::Forall(xs, p) => {
+                write!(f, "forall {}: ", Sep(xs))?;
+                p.fmt_surface(f, operand)
+            }
+            UntypedForm::Exist(xs, p) => {
+                write!(f, "exist {}: ", Sep(xs))?;
+                p.fmt_surface(f, operand)
+            }
+            UntypedForm::ForG(rf, ags, p) => {
+                write!(f, "agents {rf}")?;
+                match ags {
+                    AgentSet::All { default: false } => {}
+                    AgentSet::All { default: true } => write!(f, " in *")?,
+                    AgentSet::Of(ags) => write!(f, " in {}", Sep(ags))?,
+                }
+                write!(f, ": ")?;
+                p.fmt_surface(f, operand)
+            }
+            UntypedForm::K(rf, p) => write!(f, "K[{rf}: {p}]"),
+            UntypedForm::Kw(rf, p) => write!(f, "Kw[{rf}: {p}]"),
+            UntypedForm::B(rf, p) => write!(f, "B[{rf}: {p}]"),
+            UntypedForm::CK(rfs, p) => write!(f, "C[{}: {p}]", Sep(rfs)),
+            UntypedForm::DK(rfs, p) => write!(f, "D[{}: {p}]", Sep(rfs)),
+            UntypedForm::Conj(..)
+            | UntypedForm::Disj(..)
+            | UntypedForm::Xor(..)
+            | UntypedForm::Impl(..)
+            | UntypedForm::BiImpl(..) => unreachable!("formatted as infix"),
+        }
+    }
+}
+
+/// Comma-separated items.
+struct Sep<'s, T>(&'s [T]);
+
+impl<T> Display for Sep<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, x) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{x}")?;
+        }
+        Ok(())
+    }
+}
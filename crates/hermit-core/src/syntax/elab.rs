@@ -1,155 +1,428 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
 use immutable_list::List;
 use iter_unique_ord::IterUniqueOrd;
 
-use super::{TypedForm, TypedMeta, UntypedForm, UntypedMeta, UntypedRef};
+use super::{
+    AgentSet, Environment, Principal, TypedForm, TypedMeta, UntypedForm, UntypedMeta, UntypedRef,
+};
 
 /// An error that arises during type-checking.
-#[derive(Debug, Clone)]
-pub enum ElabError<A> {
+///
+/// Each error carries the offending agent or location. Those parsed from attributes are
+/// identifiers, which carry the `hermit_syntax::lang::Spanned` location of their token.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, thiserror::Error)]
+pub enum ElabError<A, L> {
     /// The use of an unbound agent reference.
-    FreeAgent(AgentContext<A>, A),
+    #[error("unbound agent `{0}`")]
+    FreeAgent(A),
+    /// The use of a location that has no owners.
+    #[error("unknown location `{0}`")]
+    UnknownProp(L),
+    /// An `agents` quantifier that ranges over no agents.
+    #[error("`{0}` ranges over no agents")]
+    EmptyAgents(A),
+    /// A location bound twice by the same quantifier.
+    #[error("`{0}` is bound twice by the same quantifier")]
+    DuplicateBinder(L),
+    /// A location bound by a quantifier inside another that binds it.
+    #[error("`{0}` shadows the binder of an enclosing quantifier")]
+    ShadowedBinder(L),
+    /// An `agents` binder named after an agent, a built-in agent or the binder of an enclosing
+    /// `agents`.
+    #[error("`{0}` shadows an agent of the same name")]
+    ShadowedAgent(A),
+    /// An agent of common or distributed knowledge that owns no location.
+    #[error("agent `{0}` owns nothing")]
+    NotOwner(A),
+    /// A group used where a single agent is expected.
+    #[error("`{0}` is a group, not an agent")]
+    GroupAgent(A),
+    /// A belief of an agent, for which assertions declare no plausible states, so that it would
+    /// mean the same as knowledge.
+    #[error("the belief of `{0}` cannot be checked, since no states are declared plausible")]
+    Belief(A),
+}
+
+impl<A, L> ElabError<A, L> {
+    /// The offending agent, unless the error is about a location.
+    pub fn agent(&self) -> Option<&A> {
+        match self {
+            ElabError::FreeAgent(ag)
+            | ElabError::EmptyAgents(ag)
+            | ElabError::ShadowedAgent(ag)
+            | ElabError::NotOwner(ag)
+            | ElabError::GroupAgent(ag)
+            | ElabError::Belief(ag) => Some(ag),
+            ElabError::UnknownProp(_)
+            | ElabError::DuplicateBinder(_)
+            | ElabError::ShadowedBinder(_) => None,
+        }
+    }
 }
 
+/// The errors found during type-checking, in the order they were found.
+pub type ElabErrors<A, L> = Vec<ElabError<A, L>>;
+
 impl<A, L> UntypedMeta<A, L>
 where
-    A: Ord + Clone,
+    A: Ord + Clone + AsRef<str>,
     L: Ord + Clone,
 {
-    pub fn elab(self) -> Result<TypedMeta<A, L>, ElabError<A>> {
+    /// Elaborate into the equivalent [`TypedMeta`], collecting every error.
+    ///
+    /// The `have` and `ensure` assertions are put in [`TypedForm::canonical`] form, and those
+    /// that are equal up to reordering are kept once.
+    pub fn elab(self) -> Result<TypedMeta<Principal<A>, L>, ElabErrors<A, L>> {
         let UntypedMeta {
             owners,
+            groups,
+            observers,
             haves,
             ensures,
             forgets,
         } = self;
 
-        let ctx: AgentContext<_> = owners
-            .iter()
-            .flat_map(|(_, ags)| ags)
-            .unique_ord()
-            .cloned()
-            .map(|ag| (ag.clone(), ag))
-            .collect();
+        let mut elab = Elab::new(&owners, &groups);
 
         // elab `have` and `ensure` assertions
-        let haves = haves
+        let haves: Vec<_> = haves
             .into_iter()
-            .map(|p| p.elab(ctx.clone()))
-            .collect::<Result<_, _>>()?;
+            .map(|p| elab.form(p, elab.context()).canonical())
+            .collect();
         let ensures: Vec<_> = ensures
             .into_iter()
-            .map(|p| p.elab(ctx.clone()))
-            .collect::<Result<_, _>>()?;
+            .map(|p| elab.form(p, elab.context()).canonical())
+            .collect();
+        let haves = haves.into_iter().unique_ord().collect();
+        let ensures = ensures.into_iter().unique_ord().collect();
 
         // elab `forget` assertions.
+        for forget in &forgets {
+            elab.prop(&forget.subject);
+            forget.dependencies.iter().for_each(|b| elab.prop(b));
+        }
         let forgets = forgets.into_iter().map(|f| f.elab()).collect();
 
-        Ok(TypedMeta::new(owners, haves, ensures, forgets))
+        elab.finish()?;
+        Ok(TypedMeta::new(owners, haves, ensures, forgets).with_observers(observers))
     }
 }
 
 impl<A, L> UntypedForm<A, L>
 where
-    A: Ord + Clone,
-    L: Clone,
+    A: Ord + Clone + AsRef<str>,
+    L: Ord + Clone,
 {
-    /// Elaborate into the equivalent [`TypedForm`].
-    pub fn elab(self, atx: AgentContext<A>) -> Result<TypedForm<A, L>, ElabError<A>> {
-        let form = match self {
-            UntypedForm::Top => TypedForm::Top,
-            UntypedForm::Bot => TypedForm::Bot,
-            UntypedForm::Prop(b) => TypedForm::Prop(b),
-            UntypedForm::Neg(p) => TypedForm::Neg(Box::new(p.elab(atx)?)),
+    /// Elaborate into the equivalent [`TypedForm`], for the given owners of each location and
+    /// members of each group, collecting every error.
+    pub fn elab(
+        self,
+        owners: &BTreeMap<L, BTreeSet<Principal<A>>>,
+        groups: &BTreeMap<A, Vec<A>>,
+    ) -> Result<TypedForm<Principal<A>, L>, ElabErrors<A, L>> {
+        let mut elab = Elab::new(owners, groups);
+        let form = elab.form(self, elab.context());
+        elab.finish()?;
+        Ok(form)
+    }
+}
+
+/// The state of elaborating formulas.
+struct Elab<'o, A, L> {
+    owners: &'o BTreeMap<L, BTreeSet<Principal<A>>>,
+    /// The agents that own some location.
+    agents: BTreeSet<&'o Principal<A>>,
+    /// The members of each named group.
+    groups: Rc<BTreeMap<A, Vec<A>>>,
+    /// The locations bound by the enclosing quantifiers.
+    bound: Vec<L>,
+    errors: Vec<ElabError<A, L>>,
+}
 
-            UntypedForm::Conj(p1, p2) => {
-                TypedForm::Conj(vec![p1.elab(atx.clone())?, p2.elab(atx)?])
+impl<'o, A, L> Elab<'o, A, L>
+where
+    A: Ord + Clone + AsRef<str>,
+    L: Ord + Clone,
+{
+    fn new(owners: &'o BTreeMap<L, BTreeSet<Principal<A>>>, groups: &BTreeMap<A, Vec<A>>) -> Self {
+        let agents: BTreeSet<_> = owners.values().flatten().collect();
+
+        // a group may not be named after an agent.
+        let errors = groups
+            .keys()
+            .filter(|&name| {
+                agents.contains(&Principal::Named(name.clone()))
+                    || Environment::named(name.as_ref()).is_some()
+            })
+            .map(|name| ElabError::ShadowedAgent(name.clone()))
+            .collect();
+
+        Self {
+            owners,
+            agents,
+            groups: Rc::new(groups.clone()),
+            bound: Vec::new(),
+            errors,
+        }
+    }
+
+    /// The context in which the name of each named agent refers to it, and that of each group
+    /// to its members.
+    fn context(&self) -> AgentContext<A> {
+        let atx: AgentContext<A> = self
+            .agents
+            .iter()
+            .filter_map(|&ag| Some((ag.name()?.clone(), ag.clone())))
+            .collect();
+        AgentContext {
+            groups: self.groups.clone(),
+            ..atx
+        }
+    }
+
+    /// The errors, in the order they were found.
+    fn finish(self) -> Result<(), ElabErrors<A, L>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// Elaborate `form` in the context `atx`, keeping its errors apart from those found so far.
+    fn errors_of(
+        &mut self,
+        form: UntypedForm<A, L>,
+        atx: AgentContext<A>,
+    ) -> (TypedForm<Principal<A>, L>, ElabErrors<A, L>) {
+        let outer = std::mem::take(&mut self.errors);
+        let form = self.form(form, atx);
+        (form, std::mem::replace(&mut self.errors, outer))
+    }
+
+    /// Check that `b` is a location.
+    fn prop(&mut self, b: &L) {
+        if !self.owners.contains_key(b) {
+            self.errors.push(ElabError::UnknownProp(b.clone()));
+        }
+    }
+
+    /// Resolve `rf`, or take it as the name of an agent if it is unbound.
+    fn agent(&mut self, rf: UntypedRef<A>, atx: &AgentContext<A>) -> Principal<A> {
+        let UntypedRef(name) = &rf;
+        let name = name.clone();
+        rf.elab(atx).unwrap_or_else(|err| {
+            if atx.group(&name).is_some() {
+                self.errors.push(ElabError::GroupAgent(name.clone()));
+            } else {
+                self.errors.push(err);
             }
-            UntypedForm::Disj(p1, p2) => {
-                TypedForm::Disj(vec![p1.elab(atx.clone())?, p2.elab(atx)?])
+            Principal::Named(name)
+        })
+    }
+
+    /// The agent of a name in the set of an `agents` quantifier, a declared agent before a
+    /// built-in one.
+    fn principal(&self, name: A) -> Principal<A> {
+        match Environment::named(name.as_ref()) {
+            Some(env) if !self.agents.contains(&Principal::Named(name.clone())) => {
+                Principal::Env(env)
             }
-            UntypedForm::Xor(p1, p2) => TypedForm::Xor(vec![p1.elab(atx.clone())?, p2.elab(atx)?]),
+            _ => Principal::Named(name),
+        }
+    }
 
-            UntypedForm::Impl(p1, p2) => {
-                TypedForm::Impl(Box::new(p1.elab(atx.clone())?), Box::new(p2.elab(atx)?))
+    /// Resolve the agents of a group, which must own some location.
+    fn group(&mut self, rfs: Vec<UntypedRef<A>>, atx: &AgentContext<A>) -> Vec<Principal<A>> {
+        atx.expand(rfs.into_iter().map(|UntypedRef(rf)| rf))
+            .into_iter()
+            .map(UntypedRef)
+            .map(|rf| {
+                let name = rf.0.clone();
+                let ag = self.agent(rf, atx);
+                if !self.agents.contains(&ag) {
+                    let name = ag.name().cloned().unwrap_or(name);
+                    self.errors.push(ElabError::NotOwner(name));
+                }
+                ag
+            })
+            .collect()
+    }
+
+    /// Check the locations bound by a quantifier, then elaborate its body.
+    fn quantifier(
+        &mut self,
+        bs: &[L],
+        p: UntypedForm<A, L>,
+        atx: AgentContext<A>,
+    ) -> TypedForm<Principal<A>, L> {
+        for (i, b) in bs.iter().enumerate() {
+            self.prop(b);
+            if bs[..i].contains(b) {
+                self.errors.push(ElabError::DuplicateBinder(b.clone()));
+            } else if self.bound.contains(b) {
+                self.errors.push(ElabError::ShadowedBinder(b.clone()));
             }
-            UntypedForm::BiImpl(p1, p2) => {
-                TypedForm::Equiv(Box::new(p1.elab(atx.clone())?), Box::new(p2.elab(atx)?))
+        }
+
+        let depth = self.bound.len();
+        self.bound.extend(bs.iter().cloned());
+        let form = self.form(p, atx);
+        self.bound.truncate(depth);
+        form
+    }
+
+    /// Elaborate `form` in the context `atx`.
+    fn form(
+        &mut self,
+        form: UntypedForm<A, L>,
+        atx: AgentContext<A>,
+    ) -> TypedForm<Principal<A>, L> {
+        let sub = |elab: &mut Self, p: Box<UntypedForm<A, L>>| Box::new(elab.form(*p, atx.clone()));
+        match form {
+            UntypedForm::Top => TypedForm::Top,
+            UntypedForm::Bot => TypedForm::Bot,
+            UntypedForm::Prop(b) => {
+                self.prop(&b);
+                TypedForm::Prop(b)
             }
+            UntypedForm::Neg(p) => TypedForm::Neg(sub(self, p)),
+
+            UntypedForm::Conj(p1, p2) => TypedForm::Conj(vec![*sub(self, p1), *sub(self, p2)]),
+            UntypedForm::Disj(p1, p2) => TypedForm::Disj(vec![*sub(self, p1), *sub(self, p2)]),
+            UntypedForm::Xor(p1, p2) => TypedForm::Xor(vec![*sub(self, p1), *sub(self, p2)]),
+
+            UntypedForm::Impl(p1, p2) => TypedForm::Impl(sub(self, p1), sub(self, p2)),
+            UntypedForm::BiImpl(p1, p2) => TypedForm::Equiv(sub(self, p1), sub(self, p2)),
 
-            UntypedForm::Forall(ps, p) => TypedForm::Forall(ps, Box::new(p.elab(atx.clone())?)),
-            UntypedForm::Exist(ps, p) => TypedForm::Exist(ps, Box::new(p.elab(atx.clone())?)),
+            UntypedForm::Forall(bs, p) => {
+                let p = self.quantifier(&bs, *p, atx);
+                TypedForm::Forall(bs, Box::new(p))
+            }
+            UntypedForm::Exist(bs, p) => {
+                let p = self.quantifier(&bs, *p, atx);
+                TypedForm::Exist(bs, Box::new(p))
+            }
 
             UntypedForm::ForG(rf, set, p) => {
-                let ps = if set.is_empty() {
-                    atx.iter()
-                        .map(|(_, ag)| ag)
-                        .unique_ord()
-                        .map(|ag| p.clone().elab(atx.extend(rf.clone(), ag.clone())))
-                        .collect::<Result<_, _>>()?
-                } else {
-                    set.into_iter()
-                        .map(|ag| p.clone().elab(atx.extend(rf.clone(), ag)))
-                        .collect::<Result<_, _>>()?
+                if atx.iter().any(|(r, _)| *r == rf)
+                    || atx.group(&rf).is_some()
+                    || Environment::named(rf.as_ref()).is_some()
+                {
+                    self.errors.push(ElabError::ShadowedAgent(rf.clone()));
+                }
+                let set: Vec<_> = match set {
+                    AgentSet::All { default } => self
+                        .agents
+                        .iter()
+                        .filter(|ag| ag.is_quantified(default))
+                        .map(|&ag| ag.clone())
+                        .collect(),
+                    AgentSet::Of(ags) => atx
+                        .expand(ags)
+                        .into_iter()
+                        .map(|ag| self.principal(ag))
+                        .collect(),
                 };
+                if set.is_empty() {
+                    // still check the body, with the binder standing for itself.
+                    self.errors.push(ElabError::EmptyAgents(rf.clone()));
+                    self.form(*p, atx.extend(rf.clone(), Principal::Named(rf)));
+                    return TypedForm::Conj(vec![]);
+                }
+
+                // the body is checked once for each agent, but each of its tokens is reported
+                // at most as often as a single check finds it.
+                let mut found = Vec::new();
+                let ps = set
+                    .into_iter()
+                    .map(|ag| {
+                        let (form, errors) =
+                            self.errors_of((*p).clone(), atx.extend(rf.clone(), ag));
+                        merge_errors(&mut found, errors);
+                        form
+                    })
+                    .collect();
+                self.errors.extend(found);
                 TypedForm::Conj(ps)
             }
 
-            UntypedForm::K(rf, p) => TypedForm::K(rf.elab(atx.clone())?, Box::new(p.elab(atx)?)),
-            UntypedForm::CK(rfs, p) => TypedForm::CK(
-                rfs.into_iter()
-                    .map(|rf| rf.elab(atx.clone()))
-                    .collect::<Result<_, _>>()?,
-                Box::new(p.elab(atx)?),
-            ),
-            UntypedForm::DK(rfs, p) => TypedForm::DK(
-                rfs.into_iter()
-                    .map(|rf| rf.elab(atx.clone()))
-                    .collect::<Result<_, _>>()?,
-                Box::new(p.elab(atx)?),
-            ),
-        };
+            UntypedForm::K(rf, p) => TypedForm::K(self.agent(rf, &atx), sub(self, p)),
+            UntypedForm::Kw(rf, p) => TypedForm::Kw(self.agent(rf, &atx), sub(self, p)),
+            UntypedForm::B(rf, p) => {
+                self.errors.push(ElabError::Belief(rf.0.clone()));
+                TypedForm::B(self.agent(rf, &atx), sub(self, p))
+            }
+            UntypedForm::CK(rfs, p) => TypedForm::CK(self.group(rfs, &atx), sub(self, p)),
+            UntypedForm::DK(rfs, p) => TypedForm::DK(self.group(rfs, &atx), sub(self, p)),
+        }
+    }
+}
 
-        Ok(form)
+/// Add to `found` the errors of another check of the same formula, keeping each as many times as
+/// a single check finds it.
+fn merge_errors<E: Ord + Clone>(found: &mut Vec<E>, errors: Vec<E>) {
+    let mut seen = BTreeMap::new();
+    for err in errors {
+        let n = seen.entry(err.clone()).or_insert(0);
+        *n += 1;
+        if found.iter().filter(|&found| *found == err).count() < *n {
+            found.push(err);
+        }
     }
 }
 
 impl<A> UntypedRef<A>
 where
-    A: Ord + Clone,
+    A: Ord + Clone + AsRef<str>,
 {
-    pub fn elab(self, atx: AgentContext<A>) -> Result<A, ElabError<A>> {
+    /// Resolve the reference in `atx`, or else to the built-in agent of its name.
+    pub fn elab<L>(self, atx: &AgentContext<A>) -> Result<Principal<A>, ElabError<A, L>> {
         let Self(rf) = self;
-        atx.lookup(rf).cloned()
+        match atx.lookup(rf.clone()) {
+            Ok(ag) => Ok(ag.clone()),
+            Err(err) => Environment::named(rf.as_ref())
+                .map(Principal::Env)
+                .ok_or(err),
+        }
     }
 }
 
+/// The agents that names refer to, innermost binder first, and the members of each group.
 #[derive(Debug, Clone)]
 pub struct AgentContext<A> {
-    inner: List<(A, A)>,
+    inner: List<(A, Principal<A>)>,
+    groups: Rc<BTreeMap<A, Vec<A>>>,
 }
 
 impl<A> AgentContext<A> {
     pub fn empty() -> Self {
-        Self { inner: List::new() }
+        Self {
+            inner: List::new(),
+            groups: Rc::new(BTreeMap::new()),
+        }
     }
 
-    pub fn extend(&self, rf: A, ag: A) -> Self {
-        let Self { inner } = self;
+    pub fn extend(&self, rf: A, ag: Principal<A>) -> Self {
+        let Self { inner, groups } = self;
 
         Self {
             inner: inner.cons((rf, ag)),
+            groups: groups.clone(),
         }
     }
 
-    pub fn list(&self) -> &List<(A, A)> {
+    pub fn list(&self) -> &List<(A, Principal<A>)> {
         &self.inner
     }
 
-    pub fn to_list(self) -> List<(A, A)> {
+    pub fn to_list(self) -> List<(A, Principal<A>)> {
         self.inner
     }
 
-    pub fn iter(&self) -> immutable_list::Iter<'_, (A, A)> {
+    pub fn iter(&self) -> immutable_list::Iter<'_, (A, Principal<A>)> {
         self.list().iter()
     }
 }
@@ -158,11 +431,41 @@ impl<A> AgentContext<A>
 where
     A: Eq + Clone,
 {
-    pub fn lookup(&self, rf: A) -> Result<&A, ElabError<A>> {
+    pub fn lookup<L>(&self, rf: A) -> Result<&Principal<A>, ElabError<A, L>> {
         self.inner
             .iter()
             .find_map(|(r, a)| if *r == rf { Some(a) } else { None })
-            .ok_or_else(|| ElabError::FreeAgent(self.clone(), rf))
+            .ok_or_else(|| ElabError::FreeAgent(rf))
+    }
+}
+
+impl<A> AgentContext<A>
+where
+    A: Ord + Clone,
+{
+    /// The members of the group `name`, unless an enclosing binder shadows it.
+    pub fn group(&self, name: &A) -> Option<&[A]> {
+        if self.inner.iter().any(|(r, _)| r == name) {
+            return None;
+        }
+        self.groups.get(name).map(Vec::as_slice)
+    }
+
+    /// Replace each group in `names` by its members, keeping the first of each name.
+    pub fn expand(&self, names: impl IntoIterator<Item = A>) -> Vec<A> {
+        let mut out: Vec<A> = Vec::new();
+        for name in names {
+            let members = match self.group(&name) {
+                Some(members) => members.to_vec(),
+                None => vec![name],
+            };
+            for member in members {
+                if !out.contains(&member) {
+                    out.push(member);
+                }
+            }
+        }
+        out
     }
 }
 
@@ -173,17 +476,203 @@ impl<A> Default for AgentContext<A> {
 }
 
 impl<'c, A> IntoIterator for &'c AgentContext<A> {
-    type Item = &'c (A, A);
-    type IntoIter = immutable_list::Iter<'c, (A, A)>;
+    type Item = &'c (A, Principal<A>);
+    type IntoIter = immutable_list::Iter<'c, (A, Principal<A>)>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
 
-impl<A> FromIterator<(A, A)> for AgentContext<A> {
-    fn from_iter<T: IntoIterator<Item = (A, A)>>(iter: T) -> Self {
+impl<A> FromIterator<(A, Principal<A>)> for AgentContext<A> {
+    fn from_iter<T: IntoIterator<Item = (A, Principal<A>)>>(iter: T) -> Self {
         iter.into_iter()
             .fold(Self::empty(), |ctx, (rf, ag)| ctx.extend(rf, ag))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use maplit::{btreemap, btreeset};
+
+    use super::*;
+
+    #[test]
+    fn test_elab_errors() {
+        let owners = btreemap! {
+            "x" => btreeset! {Principal::Named("a")},
+            "y" => btreeset! {Principal::Named("a"), Principal::Named("b")},
+        };
+        let k = |ag, p| UntypedForm::K(UntypedRef(ag), Box::new(p));
+
+        // agents g in a, c: forall x, x: forall y, z: C[g, b: x] & K[h: y]
+        let form: UntypedForm<&str, &str> = UntypedForm::ForG(
+            "g",
+            AgentSet::Of(vec!["a", "c"]),
+            Box::new(UntypedForm::Forall(
+                vec!["x", "x"],
+                Box::new(UntypedForm::Forall(
+                    vec!["y", "z"],
+                    Box::new(UntypedForm::Conj(
+                        Box::new(UntypedForm::CK(
+                            vec![UntypedRef("g"), UntypedRef("b")],
+                            Box::new(UntypedForm::Prop("x")),
+                        )),
+                        Box::new(k("h", UntypedForm::Prop("y"))),
+                    )),
+                )),
+            )),
+        );
+        let errors = vec![
+            ElabError::DuplicateBinder("x"),
+            ElabError::UnknownProp("z"),
+            ElabError::FreeAgent("h"),
+            ElabError::NotOwner("c"),
+        ];
+        assert_eq!(form.elab(&owners, &BTreeMap::new()), Err(errors));
+
+        let all = AgentSet::All { default: false };
+        let form = UntypedForm::ForG("a", all, Box::new(k("a", UntypedForm::Prop("x"))));
+        assert_eq!(
+            form.clone().elab(&owners, &BTreeMap::new()),
+            Err(vec![ElabError::ShadowedAgent("a")])
+        );
+        assert_eq!(
+            form.elab(&BTreeMap::new(), &BTreeMap::new()),
+            Err(vec![
+                ElabError::EmptyAgents("a"),
+                ElabError::UnknownProp("x")
+            ])
+        );
+
+        let form = UntypedForm::B(UntypedRef("a"), Box::new(UntypedForm::Prop("x")));
+        assert_eq!(
+            form.elab(&owners, &BTreeMap::new()),
+            Err(vec![ElabError::Belief("a")])
+        );
+
+        // each use of an unbound agent is reported, but only once under `agents`.
+        let uses = UntypedForm::Conj(
+            Box::new(k("h", UntypedForm::Prop("x"))),
+            Box::new(k("h", UntypedForm::Prop("y"))),
+        );
+        let form = UntypedForm::ForG("g", AgentSet::Of(vec!["a", "b"]), Box::new(uses));
+        assert_eq!(
+            form.elab(&owners, &BTreeMap::new()),
+            Err(vec![ElabError::FreeAgent("h"), ElabError::FreeAgent("h")])
+        );
+    }
+
+    #[test]
+    fn test_elab_quantified() {
+        let owners = btreemap! {
+            "x" => btreeset! {Principal::Named("a"), Principal::Default},
+            "y" => btreeset! {Principal::Pure},
+        };
+        let body = Box::new(UntypedForm::K(
+            UntypedRef("g"),
+            Box::new(UntypedForm::Prop("x")),
+        ));
+        let k = |ag| TypedForm::K(ag, Box::new(TypedForm::Prop("x")));
+
+        // agents g: K[g: x]
+        let form = UntypedForm::ForG("g", AgentSet::All { default: false }, body.clone());
+        assert_eq!(
+            form.elab(&owners, &BTreeMap::new()),
+            Ok(TypedForm::Conj(vec![k(Principal::Named("a"))]))
+        );
+
+        // agents g in *: K[g: x]
+        let form = UntypedForm::ForG("g", AgentSet::All { default: true }, body);
+        assert_eq!(
+            form.elab(&owners, &BTreeMap::new()),
+            Ok(TypedForm::Conj(vec![
+                k(Principal::Named("a")),
+                k(Principal::Default)
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_elab_environment() {
+        let owners = btreemap! {
+            "pwd" => btreeset! {Principal::Named("a")},
+            "out" => btreeset! {Principal::Env(Environment::Stdout)},
+        };
+        let k = |ag, b| UntypedForm::K(UntypedRef(ag), Box::new(UntypedForm::Prop(b)));
+        let typed_k = |ag, b| TypedForm::K(ag, Box::new(TypedForm::Prop(b)));
+
+        // !K[network: pwd]
+        let form = UntypedForm::Neg(Box::new(k("network", "pwd")));
+        assert_eq!(
+            form.elab(&owners, &BTreeMap::new()),
+            Ok(TypedForm::Neg(Box::new(typed_k(
+                Principal::Env(Environment::Network),
+                "pwd"
+            ))))
+        );
+
+        // agents g: K[g: pwd]
+        let form = UntypedForm::ForG(
+            "g",
+            AgentSet::All { default: false },
+            Box::new(k("g", "pwd")),
+        );
+        assert_eq!(
+            form.elab(&owners, &BTreeMap::new()),
+            Ok(TypedForm::Conj(vec![
+                typed_k(Principal::Named("a"), "pwd"),
+                typed_k(Principal::Env(Environment::Stdout), "pwd"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_elab_groups() {
+        let owners = btreemap! {
+            "x" => btreeset! {Principal::Named("db"), Principal::Named("cache")},
+            "y" => btreeset! {Principal::Named("a")},
+        };
+        let groups = btreemap! {"backend" => vec!["db", "cache"]};
+        let k = |ag, b| UntypedForm::K(UntypedRef(ag), Box::new(UntypedForm::Prop(b)));
+        let typed_k = |ag, b| TypedForm::K(Principal::Named(ag), Box::new(TypedForm::Prop(b)));
+
+        // agents g in backend: K[g: x]
+        let form = UntypedForm::ForG("g", AgentSet::Of(vec!["backend"]), Box::new(k("g", "x")));
+        assert_eq!(
+            form.elab(&owners, &groups),
+            Ok(TypedForm::Conj(vec![
+                typed_k("db", "x"),
+                typed_k("cache", "x")
+            ]))
+        );
+
+        // C[backend, a: y]
+        let form = UntypedForm::CK(
+            vec![UntypedRef("backend"), UntypedRef("a")],
+            Box::new(UntypedForm::Prop("y")),
+        );
+        let members = vec![
+            Principal::Named("db"),
+            Principal::Named("cache"),
+            Principal::Named("a"),
+        ];
+        assert_eq!(
+            form.elab(&owners, &groups),
+            Ok(TypedForm::CK(members, Box::new(TypedForm::Prop("y"))))
+        );
+
+        // K[backend: x]
+        assert_eq!(
+            k("backend", "x").elab(&owners, &groups),
+            Err(vec![ElabError::GroupAgent("backend")])
+        );
+
+        // a group named after an agent.
+        let groups = btreemap! {"a" => vec!["db"]};
+        assert_eq!(
+            UntypedForm::Top.elab(&owners, &groups),
+            Err(vec![ElabError::ShadowedAgent("a")])
+        );
+    }
+}
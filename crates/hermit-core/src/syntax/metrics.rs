@@ -0,0 +1,121 @@
+use epistemic::Metrics;
+
+use super::visit::{walk_form, Visitor};
+use super::{AgentSet, UntypedForm, UntypedRef};
+
+impl<A, L> UntypedForm<A, L>
+where
+    A: Ord,
+    L: Ord,
+{
+    /// Measure the formula, as [`epistemic::Form::metrics`].
+    ///
+    /// The agents bound by `agents` are not counted, but those of the sets they range over are.
+    pub fn metrics(&self) -> Metrics {
+        Self::metrics_all([self])
+    }
+
+    /// Measure formulas together, as if they were conjuncts.
+    pub fn metrics_all<'f>(forms: impl IntoIterator<Item = &'f Self>) -> Metrics
+    where
+        A: 'f,
+        L: 'f,
+    {
+        let mut measure = Measure::default();
+        forms.into_iter().for_each(|form| measure.visit_form(form));
+        measure.measure.metrics()
+    }
+}
+
+/// A [`Visitor`] measuring formulas with the bookkeeping of [`epistemic::Measure`].
+struct Measure<'f, A, L> {
+    measure: epistemic::Measure<'f, A, L>,
+    /// The agents bound around the current subformula.
+    bound: Vec<&'f A>,
+}
+
+impl<A, L> Default for Measure<'_, A, L> {
+    fn default() -> Self {
+        Self {
+            measure: epistemic::Measure::default(),
+            bound: Vec::new(),
+        }
+    }
+}
+
+impl<'f, A, L> Visitor<'f, A, L> for Measure<'f, A, L>
+where
+    A: Ord,
+    L: Ord,
+{
+    fn visit_form(&mut self, form: &'f UntypedForm<A, L>) {
+        // the modal and common knowledge nesting added by `form`.
+        let (modal, common) = match form {
+            UntypedForm::K(..) | UntypedForm::Kw(..) | UntypedForm::B(..) | UntypedForm::DK(..) => {
+                (1, 0)
+            }
+            UntypedForm::CK(..) => (1, 1),
+            _ => (0, 0),
+        };
+
+        let outer = self.measure.enter(modal, common, 0);
+        if let UntypedForm::ForG(rf, ags, p) = form {
+            if let AgentSet::Of(ags) = ags {
+                self.measure.agents(ags);
+            }
+            self.bound.push(rf);
+            self.visit_form(p);
+            self.bound.pop();
+        } else {
+            walk_form(self, form);
+        }
+        self.measure.leave(outer);
+    }
+
+    fn visit_ref(&mut self, rf: &'f UntypedRef<A>) {
+        if !self.bound.contains(&&rf.0) {
+            self.measure.agents([&rf.0]);
+        }
+    }
+
+    fn visit_prop(&mut self, b: &'f L) {
+        self.measure.vocab([b]);
+    }
+
+    fn visit_binder(&mut self, bs: &'f [L]) {
+        self.measure.vocab(bs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        // forall x in {a, b}: K x (CK c (p ∧ q))
+        let form: UntypedForm<&str, &str> = UntypedForm::ForG(
+            "x",
+            AgentSet::Of(vec!["a", "b"]),
+            Box::new(UntypedForm::K(
+                UntypedRef("x"),
+                Box::new(UntypedForm::CK(
+                    vec![UntypedRef("c")],
+                    Box::new(UntypedForm::Conj(
+                        Box::new(UntypedForm::Prop("p")),
+                        Box::new(UntypedForm::Prop("q")),
+                    )),
+                )),
+            )),
+        );
+        let metrics = Metrics {
+            size: 6,
+            modal_depth: 2,
+            common_depth: 1,
+            agents: 3,
+            vocab: 2,
+            announcement_depth: 0,
+        };
+        assert_eq!(form.metrics(), metrics);
+    }
+}
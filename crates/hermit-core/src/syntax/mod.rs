@@ -1,7 +1,13 @@
+mod agent;
+mod display;
+mod metrics;
+mod polarity;
 mod typed;
 mod untyped;
 
 pub mod elab;
+pub mod visit;
 
+pub use agent::{Environment, Principal};
 pub use typed::*;
 pub use untyped::*;
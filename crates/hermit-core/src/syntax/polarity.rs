@@ -0,0 +1,60 @@
+use epistemic::Polarity;
+
+use super::UntypedForm;
+
+impl<A, L> UntypedForm<A, L> {
+    /// The knowledge modalities of the formula, with the polarity of their position, as
+    /// [`epistemic::Form::knowledge`].
+    pub fn knowledge(&self) -> Vec<(Polarity, &Self)> {
+        let mut out = Vec::new();
+        self.knowledge_in(Polarity::Positive, &mut out);
+        out
+    }
+
+    /// The knowledge modalities in positive position, which assert that an agent knows something.
+    ///
+    /// Such assertions are unsound when information flow is over-approximated, since an agent
+    /// may be found to learn data that never reaches it.
+    pub fn positive_knowledge(&self) -> Vec<&Self> {
+        self.knowledge()
+            .into_iter()
+            .filter(|(pol, _)| pol.is_positive())
+            .map(|(_, p)| p)
+            .collect()
+    }
+
+    fn knowledge_in<'f>(&'f self, pol: Polarity, out: &mut Vec<(Polarity, &'f Self)>) {
+        match self {
+            UntypedForm::Top | UntypedForm::Bot | UntypedForm::Prop(_) => {}
+            UntypedForm::Neg(p) => p.knowledge_in(pol.flip(), out),
+            UntypedForm::Conj(p1, p2) | UntypedForm::Disj(p1, p2) => {
+                p1.knowledge_in(pol, out);
+                p2.knowledge_in(pol, out);
+            }
+            UntypedForm::Xor(p1, p2) | UntypedForm::BiImpl(p1, p2) => {
+                p1.knowledge_in(Polarity::Mixed, out);
+                p2.knowledge_in(Polarity::Mixed, out);
+            }
+            UntypedForm::Impl(p1, p2) => {
+                p1.knowledge_in(pol.flip(), out);
+                p2.knowledge_in(pol, out);
+            }
+            UntypedForm::Forall(_, p) | UntypedForm::Exist(_, p) | UntypedForm::ForG(_, _, p) => {
+                p.knowledge_in(pol, out)
+            }
+            // beliefs may be revised by what the agent observes, so they are both.
+            UntypedForm::B(_, p) => {
+                out.push((Polarity::Mixed, self));
+                p.knowledge_in(pol, out);
+            }
+            UntypedForm::K(_, p) | UntypedForm::CK(_, p) | UntypedForm::DK(_, p) => {
+                out.push((pol, self));
+                p.knowledge_in(pol, out);
+            }
+            UntypedForm::Kw(_, p) => {
+                out.push((pol, self));
+                p.knowledge_in(Polarity::Mixed, out);
+            }
+        }
+    }
+}
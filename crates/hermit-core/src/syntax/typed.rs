@@ -1,19 +1,28 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::iter;
 
+use epistemic::ReduceError;
+use epistemic_smcdel::text::CheckInput;
+
 use crate::semantics::{
     AnnouncementFlow, AnnouncementFlowSat, Channel, Flow, Group, KnowStruct, Network, NetworkFlow,
-    Semantics,
 };
 
 pub type TypedForm<A, L> = epistemic::Form<A, L>;
 
+/// The vocabulary, law and observations of a knowledge structure.
+type Structure<A, L> = (Vec<L>, TypedForm<A, L>, Vec<(A, Vec<L>)>);
+
 #[derive(Debug, Clone)]
 pub struct TypedMeta<A, L> {
     /// The owners of each location.
     ///
     /// This map defines the set of valid agents.
     pub owners: BTreeMap<L, BTreeSet<A>>,
+    /// The agents that see when data reaches each location, besides its owners.
+    ///
+    /// Every agent sees when data reaches the locations missing from this map.
+    pub observers: BTreeMap<L, BTreeSet<A>>,
 
     /// The `have` assertions.
     pub haves: Vec<TypedForm<A, L>>,
@@ -48,21 +57,48 @@ impl<A, L> TypedMeta<A, L> {
     ) -> Self {
         Self {
             owners,
+            observers: BTreeMap::new(),
             haves,
             ensures,
             forgets,
         }
     }
+
+    /// Let only the `observers` of each location, besides its owners, see when data reaches it.
+    pub fn with_observers(mut self, observers: BTreeMap<L, BTreeSet<A>>) -> Self {
+        self.observers = observers;
+        self
+    }
 }
 
 impl<A, L> TypedMeta<A, L>
 where
     A: Ord,
+    L: Eq,
 {
     pub fn know_struct<'i, K>(&'i self) -> K
     where
         K: KnowStruct<Agent = &'i A, Prop = &'i L>,
     {
+        let (vocab, law, obs) = self.structure();
+        K::new(vocab, law, obs)
+    }
+
+    /// The knowledge structure of [`TypedMeta::know_struct`], without jobs, for exporting to
+    /// SMCDEL.
+    ///
+    /// Announcements in the law that SMCDEL's text format cannot express are reduced away first.
+    pub fn check_input(&self) -> Result<CheckInput<&A, &L>, ReduceError<&A, &L>> {
+        let (vocab, law, obs) = self.structure();
+        let law = match law.smcdel() {
+            Some(_) => law,
+            None => law.reduce_announcements()?,
+        };
+        Ok(CheckInput::new(vocab, law, obs))
+    }
+
+    /// The vocabulary, law and observations of the knowledge structure.
+    fn structure(&self) -> Structure<&A, &L> {
         let all_ags = self.owners.iter().flat_map(|(_, ags)| ags);
 
         let vocab: Vec<_> = self
@@ -77,9 +113,10 @@ where
                 .chain(self.ensures.iter())
                 .map(Into::into)
                 .collect(),
-        );
+        )
+        .simplify();
         let obs = all_ags.map(|ag| (ag, vocab.clone())).collect();
-        K::new(vocab, law, obs)
+        (vocab, law, obs)
     }
 }
 
@@ -92,7 +129,14 @@ where
         let channels = self
             .owners
             .iter()
-            .map(|(loc, group)| (loc, Channel::new(Group::new(group.iter().collect()))))
+            .map(|(loc, group)| {
+                let chan = Channel::new(Group::new(group.iter().collect()));
+                let chan = match self.observers.get(loc) {
+                    Some(observers) => chan.with_observers(observers.iter().collect()),
+                    None => chan,
+                };
+                (loc, chan)
+            })
             .collect();
         Network::new(channels)
     }
@@ -115,7 +159,10 @@ where
         NetworkFlow::new(network, flow)
     }
 
-    pub fn semantics<'i, F, K>(&'i self, flow: F) -> impl Semantics<Agent = &'i A, Prop = &'i L>
+    pub fn semantics<'i, F, K>(
+        &'i self,
+        flow: F,
+    ) -> AnnouncementFlowSat<impl AnnouncementFlow<Agent = &'i A, Location = &'i L>, K>
     where
         F: Flow<Location = &'i L>,
         K: KnowStruct<Agent = &'i A, Prop = &'i L>,
@@ -150,3 +197,23 @@ where
         iter
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::*;
+
+    #[test]
+    fn test_check_input() {
+        // a group announcement, which SMCDEL's text format cannot express.
+        let p = || Box::new(TypedForm::Prop("x"));
+        let have = TypedForm::GA(vec!["a"], p(), Box::new(TypedForm::K("a", p())));
+        let owners = BTreeMap::from([("x", BTreeSet::from(["a"]))]);
+        let meta = TypedMeta::new(owners, vec![have], vec![], vec![]);
+
+        let input = meta.check_input().unwrap();
+        assert!(input.law.smcdel().is_some());
+        assert!(input.text().is_some());
+    }
+}
@@ -1,11 +1,9 @@
 use std::collections::{BTreeMap, BTreeSet};
-use std::iter;
-
-use iter_tree::Tree;
 
 use super::typed::TypedForget;
+use super::{Environment, Principal};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UntypedForm<A, L> {
     Top,
@@ -19,16 +17,28 @@ pub enum UntypedForm<A, L> {
     BiImpl(Box<Self>, Box<Self>),
     Forall(Vec<L>, Box<Self>),
     Exist(Vec<L>, Box<Self>),
-    ForG(A, Vec<A>, Box<Self>),
+    ForG(A, AgentSet<A>, Box<Self>),
     K(UntypedRef<A>, Box<Self>),
+    Kw(UntypedRef<A>, Box<Self>),
+    B(UntypedRef<A>, Box<Self>),
     CK(Vec<UntypedRef<A>>, Box<Self>),
     DK(Vec<UntypedRef<A>>, Box<Self>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UntypedRef<A>(pub A);
 
+/// The agents that an `agents` quantifier ranges over.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AgentSet<A> {
+    /// Every agent but [`Principal::Pure`], including [`Principal::Default`] only if `default`.
+    All { default: bool },
+    /// The given named agents.
+    Of(Vec<A>),
+}
+
 /// The information for a function.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -40,7 +50,13 @@ where
     /// The owners of each location.
     ///
     /// This map defines the set of valid agents.
-    pub owners: BTreeMap<L, BTreeSet<A>>,
+    pub owners: BTreeMap<L, BTreeSet<Principal<A>>>,
+    /// The members of each named group of agents.
+    pub groups: BTreeMap<A, Vec<A>>,
+    /// The agents that see when data reaches each location, besides its owners.
+    ///
+    /// Every agent sees when data reaches the locations missing from this map.
+    pub observers: BTreeMap<L, BTreeSet<Principal<A>>>,
 
     /// The `have` assertions.
     pub haves: Vec<UntypedForm<A, L>>,
@@ -64,18 +80,42 @@ where
     L: Ord,
 {
     pub fn new(
-        owners: BTreeMap<L, BTreeSet<A>>,
+        owners: BTreeMap<L, BTreeSet<Principal<A>>>,
         haves: Vec<UntypedForm<A, L>>,
         ensures: Vec<UntypedForm<A, L>>,
         forgets: Vec<UntypedForget<L>>,
     ) -> Self {
         Self {
             owners,
+            groups: BTreeMap::new(),
+            observers: BTreeMap::new(),
             haves,
             ensures,
             forgets,
         }
     }
+
+    /// Declare the named groups of agents, which stand for their members in `agents`
+    /// quantifiers and group modalities.
+    pub fn with_groups(mut self, groups: BTreeMap<A, Vec<A>>) -> Self {
+        self.groups.extend(groups);
+        self
+    }
+
+    /// Let only `observers`, besides its owners, see when data reaches `loc`.
+    pub fn with_observers(mut self, loc: L, observers: BTreeSet<Principal<A>>) -> Self {
+        self.observers.insert(loc, observers);
+        self
+    }
+
+    /// Let `env` own `loc`, as the location of the data announced by a call to a sink.
+    pub fn with_sink(mut self, loc: L, env: Environment) -> Self {
+        self.owners
+            .entry(loc)
+            .or_default()
+            .insert(Principal::Env(env));
+        self
+    }
 }
 
 impl<L> UntypedForget<L> {
@@ -100,36 +140,3 @@ impl<A, L> Default for UntypedForm<A, L> {
         Self::Top
     }
 }
-
-impl<A, L> UntypedForm<A, L> {
-    /// Iterate over the propositions.
-    pub fn vocab<'a>(&'a self) -> Box<dyn Iterator<Item = &'a L> + 'a> {
-        match self.vocab_tree() {
-            Some(tree) => Box::new(tree.into_iter()),
-            None => Box::new(iter::empty()),
-        }
-    }
-
-    fn vocab_tree(&self) -> Option<Tree<&L>> {
-        match self {
-            UntypedForm::Top | UntypedForm::Bot => None,
-            UntypedForm::Prop(b) => Some(Tree::Leaf(b)),
-            UntypedForm::Neg(p)
-            | UntypedForm::Forall(_, p)
-            | UntypedForm::Exist(_, p)
-            | UntypedForm::ForG(_, _, p)
-            | UntypedForm::K(_, p)
-            | UntypedForm::CK(_, p)
-            | UntypedForm::DK(_, p) => p.vocab_tree().map(|n| Tree::Node(vec![n])),
-            UntypedForm::Conj(p1, p2)
-            | UntypedForm::Disj(p1, p2)
-            | UntypedForm::Xor(p1, p2)
-            | UntypedForm::Impl(p1, p2)
-            | UntypedForm::BiImpl(p1, p2) => {
-                let n1 = p1.vocab_tree()?;
-                let n2 = p2.vocab_tree()?;
-                Some(Tree::Node(vec![n1, n2]))
-            }
-        }
-    }
-}
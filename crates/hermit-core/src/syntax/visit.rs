@@ -0,0 +1,239 @@
+use std::convert::Infallible;
+
+use super::{AgentSet, UntypedForm, UntypedRef};
+
+/// A traversal of an [`UntypedForm`] by reference.
+///
+/// Implementors override only the cases they care about, calling [`walk_form`] from
+/// [`Visitor::visit_form`] to continue into the children.
+pub trait Visitor<'f, A, L> {
+    fn visit_form(&mut self, form: &'f UntypedForm<A, L>) {
+        walk_form(self, form)
+    }
+
+    /// Visit an agent reference.
+    fn visit_ref(&mut self, rf: &'f UntypedRef<A>) {
+        self.visit_agent(&rf.0)
+    }
+
+    /// Visit an agent, either referenced, bound by `agents` or in the set it ranges over.
+    fn visit_agent(&mut self, _ag: &'f A) {}
+
+    /// Visit a location occurring in a formula.
+    fn visit_prop(&mut self, _b: &'f L) {}
+
+    /// Visit the locations bound by a quantifier.
+    fn visit_binder(&mut self, _bs: &'f [L]) {}
+}
+
+/// Visit the agents, locations and subformulas of `form`.
+pub fn walk_form<'f, V, A, L>(visitor: &mut V, form: &'f UntypedForm<A, L>)
+where
+    V: Visitor<'f, A, L> + ?Sized,
+{
+    match form {
+        UntypedForm::Top | UntypedForm::Bot => {}
+        UntypedForm::Prop(b) => visitor.visit_prop(b),
+        UntypedForm::Neg(p) => visitor.visit_form(p),
+        UntypedForm::Conj(p1, p2)
+        | UntypedForm::Disj(p1, p2)
+        | UntypedForm::Xor(p1, p2)
+        | UntypedForm::Impl(p1, p2)
+        | UntypedForm::BiImpl(p1, p2) => {
+            visitor.visit_form(p1);
+            visitor.visit_form(p2);
+        }
+        UntypedForm::Forall(bs, p) | UntypedForm::Exist(bs, p) => {
+            visitor.visit_binder(bs);
+            visitor.visit_form(p);
+        }
+        UntypedForm::ForG(rf, ags, p) => {
+            visitor.visit_agent(rf);
+            if let AgentSet::Of(ags) = ags {
+                ags.iter().for_each(|ag| visitor.visit_agent(ag));
+            }
+            visitor.visit_form(p);
+        }
+        UntypedForm::K(rf, p) | UntypedForm::Kw(rf, p) | UntypedForm::B(rf, p) => {
+            visitor.visit_ref(rf);
+            visitor.visit_form(p);
+        }
+        UntypedForm::CK(rfs, p) | UntypedForm::DK(rfs, p) => {
+            rfs.iter().for_each(|rf| visitor.visit_ref(rf));
+            visitor.visit_form(p);
+        }
+    }
+}
+
+/// A rewriting of an [`UntypedForm`] by value, possibly changing the types of agents and
+/// locations.
+///
+/// Override [`Fold::fold_form`] to rewrite subformulas, calling [`fold_form`] to continue into
+/// the children.
+pub trait Fold<A, L> {
+    type Agent;
+    type Location;
+    type Error;
+
+    fn fold_form(
+        &mut self,
+        form: UntypedForm<A, L>,
+    ) -> Result<UntypedForm<Self::Agent, Self::Location>, Self::Error> {
+        fold_form(self, form)
+    }
+
+    /// Fold an agent, either referenced, bound by `agents` or in the set it ranges over.
+    fn fold_agent(&mut self, ag: A) -> Result<Self::Agent, Self::Error>;
+
+    /// Fold a location, either occurring in a formula or bound by a quantifier.
+    fn fold_prop(&mut self, b: L) -> Result<Self::Location, Self::Error>;
+}
+
+/// Fold the agents, locations and subformulas of `form`.
+pub fn fold_form<F, A, L>(
+    folder: &mut F,
+    form: UntypedForm<A, L>,
+) -> Result<UntypedForm<F::Agent, F::Location>, F::Error>
+where
+    F: Fold<A, L> + ?Sized,
+{
+    let fold_box = |folder: &mut F, p: Box<UntypedForm<A, L>>| folder.fold_form(*p).map(Box::new);
+
+    let form = match form {
+        UntypedForm::Top => UntypedForm::Top,
+        UntypedForm::Bot => UntypedForm::Bot,
+        UntypedForm::Prop(b) => UntypedForm::Prop(folder.fold_prop(b)?),
+        UntypedForm::Neg(p) => UntypedForm::Neg(fold_box(folder, p)?),
+        UntypedForm::Conj(p1, p2) => {
+            UntypedForm::Conj(fold_box(folder, p1)?, fold_box(folder, p2)?)
+        }
+        UntypedForm::Disj(p1, p2) => {
+            UntypedForm::Disj(fold_box(folder, p1)?, fold_box(folder, p2)?)
+        }
+        UntypedForm::Xor(p1, p2) => UntypedForm::Xor(fold_box(folder, p1)?, fold_box(folder, p2)?),
+        UntypedForm::Impl(p1, p2) => {
+            UntypedForm::Impl(fold_box(folder, p1)?, fold_box(folder, p2)?)
+        }
+        UntypedForm::BiImpl(p1, p2) => {
+            UntypedForm::BiImpl(fold_box(folder, p1)?, fold_box(folder, p2)?)
+        }
+        UntypedForm::Forall(bs, p) => {
+            let bs = bs
+                .into_iter()
+                .map(|b| folder.fold_prop(b))
+                .collect::<Result<_, _>>()?;
+            UntypedForm::Forall(bs, fold_box(folder, p)?)
+        }
+        UntypedForm::Exist(bs, p) => {
+            let bs = bs
+                .into_iter()
+                .map(|b| folder.fold_prop(b))
+                .collect::<Result<_, _>>()?;
+            UntypedForm::Exist(bs, fold_box(folder, p)?)
+        }
+        UntypedForm::ForG(rf, ags, p) => {
+            let rf = folder.fold_agent(rf)?;
+            let ags = match ags {
+                AgentSet::All { default } => AgentSet::All { default },
+                AgentSet::Of(ags) => AgentSet::Of(
+                    ags.into_iter()
+                        .map(|ag| folder.fold_agent(ag))
+                        .collect::<Result<_, _>>()?,
+                ),
+            };
+            UntypedForm::ForG(rf, ags, fold_box(folder, p)?)
+        }
+        UntypedForm::K(rf, p) => UntypedForm::K(fold_ref(folder, rf)?, fold_box(folder, p)?),
+        UntypedForm::Kw(rf, p) => UntypedForm::Kw(fold_ref(folder, rf)?, fold_box(folder, p)?),
+        UntypedForm::B(rf, p) => UntypedForm::B(fold_ref(folder, rf)?, fold_box(folder, p)?),
+        UntypedForm::CK(rfs, p) => {
+            let rfs = rfs
+                .into_iter()
+                .map(|rf| fold_ref(folder, rf))
+                .collect::<Result<_, _>>()?;
+            UntypedForm::CK(rfs, fold_box(folder, p)?)
+        }
+        UntypedForm::DK(rfs, p) => {
+            let rfs = rfs
+                .into_iter()
+                .map(|rf| fold_ref(folder, rf))
+                .collect::<Result<_, _>>()?;
+            UntypedForm::DK(rfs, fold_box(folder, p)?)
+        }
+    };
+    Ok(form)
+}
+
+fn fold_ref<F, A, L>(folder: &mut F, rf: UntypedRef<A>) -> Result<UntypedRef<F::Agent>, F::Error>
+where
+    F: Fold<A, L> + ?Sized,
+{
+    folder.fold_agent(rf.0).map(UntypedRef)
+}
+
+/// A [`Fold`] applying a function to each agent and to each location.
+struct MapWith<F, G>(F, G);
+
+impl<A, L, B, M, E, F, G> Fold<A, L> for MapWith<F, G>
+where
+    F: FnMut(A) -> Result<B, E>,
+    G: FnMut(L) -> Result<M, E>,
+{
+    type Agent = B;
+    type Location = M;
+    type Error = E;
+
+    fn fold_agent(&mut self, ag: A) -> Result<B, E> {
+        (self.0)(ag)
+    }
+
+    fn fold_prop(&mut self, b: L) -> Result<M, E> {
+        (self.1)(b)
+    }
+}
+
+impl<A, L> UntypedForm<A, L> {
+    /// Apply fallible functions to each agent and each location, stopping at the first error.
+    pub fn try_map<B, M, E>(
+        self,
+        agent: impl FnMut(A) -> Result<B, E>,
+        prop: impl FnMut(L) -> Result<M, E>,
+    ) -> Result<UntypedForm<B, M>, E> {
+        MapWith(agent, prop).fold_form(self)
+    }
+
+    /// Apply functions to each agent and each location.
+    pub fn map<B, M>(
+        self,
+        mut agent: impl FnMut(A) -> B,
+        mut prop: impl FnMut(L) -> M,
+    ) -> UntypedForm<B, M> {
+        let result: Result<_, Infallible> = self.try_map(|ag| Ok(agent(ag)), |b| Ok(prop(b)));
+        result.unwrap_or_else(|never| match never {})
+    }
+
+    /// Apply a function to each agent.
+    pub fn map_agents<B>(self, agent: impl FnMut(A) -> B) -> UntypedForm<B, L> {
+        self.map(agent, |b| b)
+    }
+
+    /// Apply a function to each location, including those bound by quantifiers.
+    pub fn map_props<M>(self, prop: impl FnMut(L) -> M) -> UntypedForm<A, M> {
+        self.map(|ag| ag, prop)
+    }
+
+    /// Iterate over the propositions.
+    pub fn vocab<'a>(&'a self) -> Box<dyn Iterator<Item = &'a L> + 'a> {
+        struct Vocab<'f, L>(Vec<&'f L>);
+
+        impl<'f, A, L> Visitor<'f, A, L> for Vocab<'f, L> {
+            fn visit_prop(&mut self, b: &'f L) {
+                self.0.push(b);
+            }
+        }
+
+        let mut vocab = Vocab(Vec::new());
+        vocab.visit_form(self);
+        Box::new(vocab.0.into_iter())
+    }
+}
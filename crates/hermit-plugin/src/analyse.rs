@@ -1,10 +1,146 @@
+use hermit_core::semantics::Verdict;
+use hermit_core::UntypedForm;
+use itertools::Itertools;
 use rustc_middle::ty::TyCtxt;
 
 use crate::collect::Collector;
+use crate::meta::convert::LocalFlow;
+use crate::meta::{Agent, Function, FunctionId, Meta};
+use crate::plugin::HermitPluginConfig;
 
-pub fn analyse<'tcx>(tcx: TyCtxt<'tcx>) {
+pub fn analyse(tcx: TyCtxt<'_>, config: &HermitPluginConfig) {
     let coll = Collector::new(tcx);
     let info = coll.collect();
 
-    println!("{:#?}", info);
+    for (id, fun) in &info.funs {
+        let name = tcx.def_path_str(id.0);
+        check_positive_knowledge(tcx, &name, fun, config);
+        check_ensures(tcx, &name, &info, id, fun, config);
+    }
+
+    if config.metrics {
+        let dcx = tcx.sess.dcx();
+        for (id, fun) in &info.funs {
+            let haves = fun.haves.iter().map(|have| &have.form);
+            let ensures = fun.ensures.iter().map(|ensure| &ensure.form);
+            let metrics = UntypedForm::metrics_all(haves.chain(ensures));
+            dcx.note(format!(
+                "metrics of `{}`: {}",
+                tcx.def_path_str(id.0),
+                metrics
+            ));
+        }
+    }
+}
+
+/// Reject the positive knowledge assertions in the `ensure` clauses of `fun`, or only warn about
+/// them if they are allowed.
+fn check_positive_knowledge(
+    tcx: TyCtxt<'_>,
+    name: &str,
+    fun: &Function,
+    config: &HermitPluginConfig,
+) {
+    let dcx = tcx.sess.dcx();
+    for ensure in &fun.ensures {
+        for form in ensure.form.positive_knowledge() {
+            let msg = format!(
+                "`ensure` of `{name}` asserts that {} knows something, which is unsound under \
+                 over-approximate information flow",
+                located(modality_agents(form)),
+            );
+            if config.allow_positive_knowledge {
+                dcx.warn(msg);
+            } else {
+                dcx.err(msg);
+            }
+        }
+    }
+}
+
+/// Check the `ensure` clauses of `fun` with the selected backend, reporting those that do not
+/// hold and the errors of elaborating or checking them.
+fn check_ensures(
+    tcx: TyCtxt<'_>,
+    name: &str,
+    info: &Meta,
+    id: &FunctionId,
+    fun: &Function,
+    config: &HermitPluginConfig,
+) {
+    if fun.ensures.is_empty() {
+        return;
+    }
+
+    let dcx = tcx.sess.dcx();
+    let meta = match info.untyped_meta(id, fun).elab() {
+        Ok(meta) => meta,
+        Err(errs) => {
+            for err in errs {
+                let at = err.agent().map(location).unwrap_or_default();
+                dcx.err(format!("in the assertions of `{name}`: {err}{at}"));
+            }
+            return;
+        }
+    };
+
+    for ensure in &meta.ensures {
+        let flow = LocalFlow::new(&fun.flows);
+        match config.backend.check(&meta, flow, ensure.as_ref()) {
+            Ok(Verdict::Holds) => {}
+            Ok(Verdict::Fails(cex)) => {
+                let world: Vec<_> = cex
+                    .world
+                    .iter()
+                    .map(|(loc, val)| format!("{} = {val}", loc.describe(tcx, fun)))
+                    .collect();
+                let announced: Vec<_> = cex
+                    .announcements
+                    .iter()
+                    .map(|ann| {
+                        let form = ann.form.clone().map_props(|loc| loc.describe(tcx, fun));
+                        let to = match &ann.group {
+                            Some(group) => group.iter().map(|ag| format!("`{ag}`")).join(", "),
+                            None => "everyone".to_string(),
+                        };
+                        format!("{to} learned that {form} is {}", ann.truth)
+                    })
+                    .collect();
+                let after = if announced.is_empty() {
+                    String::new()
+                } else {
+                    format!(", after {}", announced.join(", then "))
+                };
+                dcx.err(format!(
+                    "`ensure` of `{name}` does not hold when {}{after}",
+                    world.join(", "),
+                ));
+            }
+            Err(err) => {
+                dcx.err(format!("cannot check `ensure` of `{name}`: {err}"));
+            }
+        }
+    }
+}
+
+/// The agents of a knowledge or belief modality.
+fn modality_agents<L>(form: &UntypedForm<Agent, L>) -> Vec<&Agent> {
+    match form {
+        UntypedForm::K(rf, _) | UntypedForm::Kw(rf, _) | UntypedForm::B(rf, _) => vec![&rf.0],
+        UntypedForm::CK(rfs, _) | UntypedForm::DK(rfs, _) => rfs.iter().map(|rf| &rf.0).collect(),
+        _ => vec![],
+    }
+}
+
+/// Format agents with the location of their first mention.
+fn located(ags: Vec<&Agent>) -> String {
+    let names: Vec<_> = ags.iter().map(|ag| format!("`{ag}`")).collect();
+    let at = ags.first().map(|ag| location(ag)).unwrap_or_default();
+    format!("{}{at}", names.join(", "))
+}
+
+/// Format the location of the token of `ag`.
+fn location(ag: &Agent) -> String {
+    let start = &(ag.0).0.start;
+    format!(" (at {}:{})", start.line, start.column)
 }
@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, iter};
 
-use hermit_core::{UntypedForm, UntypedRef};
+use hermit_core::UntypedForm;
 use rustc_ast::{
     token::{Lit, LitKind, Token, TokenKind},
     tokenstream::TokenTree,
@@ -11,7 +11,8 @@ use rustc_lexer::unescape;
 use hermit_syntax::{
     attribute::{
         AgentMeta as AgentAttribute, Decode as DecodeAttribute, EnsureMeta as EnsureAttribute,
-        ForgetMeta as ForgetAttribute, HaveMeta as HaveAttribute,
+        ForgetMeta as ForgetAttribute, GroupMeta as GroupAttribute, HaveMeta as HaveAttribute,
+        ObserversMeta as ObserversAttribute,
     },
     TOOL,
 };
@@ -73,6 +74,10 @@ impl<'a> AttrCollector<'a> {
             Some(self.collect_ensure(&arg))
         } else if kind == ForgetAttribute::KIND {
             Some(self.collect_forget(&arg))
+        } else if kind == GroupAttribute::KIND {
+            Some(self.collect_group(&arg))
+        } else if kind == ObserversAttribute::KIND {
+            Some(self.collect_observers(&arg))
         } else {
             panic!()
         }
@@ -93,6 +98,14 @@ impl<'a> AttrCollector<'a> {
     fn collect_forget(&self, arg: &str) -> AttrInfo {
         AttrInfo::Forget(ForgetAttribute::decode(arg))
     }
+
+    fn collect_group(&self, arg: &str) -> AttrInfo {
+        AttrInfo::Group(GroupAttribute::decode(arg))
+    }
+
+    fn collect_observers(&self, arg: &str) -> AttrInfo {
+        AttrInfo::Observers(ObserversAttribute::decode(arg))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -101,12 +114,16 @@ pub enum AttrInfo {
     Have(HaveAttribute),
     Ensure(EnsureAttribute),
     Forget(ForgetAttribute),
+    Group(GroupAttribute),
+    Observers(ObserversAttribute),
 }
 
 impl AttrInfo {
     pub fn variables(&self) -> impl Iterator<Item = &hermit_syntax::lang::Ident> {
         let iter: Box<dyn Iterator<Item = _>> = match self {
-            AttrInfo::Agent(_) => Box::new(iter::empty()),
+            AttrInfo::Agent(_) | AttrInfo::Group(_) | AttrInfo::Observers(_) => {
+                Box::new(iter::empty())
+            }
             AttrInfo::Have(HaveAttribute { form }) => Box::new(form.0.vocab()),
             AttrInfo::Ensure(EnsureAttribute { form }) => Box::new(form.0.vocab()),
             AttrInfo::Forget(ForgetAttribute {
@@ -158,55 +175,7 @@ fn trans_form(
     p: UntypedForm<hermit_syntax::attribute::Ident, hermit_syntax::attribute::Ident>,
     vars: &BTreeMap<String, meta::FunctionLocation>,
 ) -> UntypedForm<meta::Agent, meta::LocalTarget> {
-    match p {
-        UntypedForm::Top => UntypedForm::Top,
-        UntypedForm::Bot => UntypedForm::Bot,
-        UntypedForm::Prop(b) => {
-            UntypedForm::Prop(meta::LocalTarget::Local(*vars.get(&b.0.value).unwrap()))
-        }
-        UntypedForm::Neg(p) => UntypedForm::Neg(Box::new(trans_form(*p, vars))),
-        UntypedForm::Conj(p1, p2) => UntypedForm::Conj(
-            Box::new(trans_form(*p1, vars)),
-            Box::new(trans_form(*p2, vars)),
-        ),
-        UntypedForm::Disj(p1, p2) => UntypedForm::Disj(
-            Box::new(trans_form(*p1, vars)),
-            Box::new(trans_form(*p2, vars)),
-        ),
-        UntypedForm::Xor(p1, p2) => UntypedForm::Xor(
-            Box::new(trans_form(*p1, vars)),
-            Box::new(trans_form(*p2, vars)),
-        ),
-        UntypedForm::Impl(p1, p2) => UntypedForm::Impl(
-            Box::new(trans_form(*p1, vars)),
-            Box::new(trans_form(*p2, vars)),
-        ),
-        UntypedForm::BiImpl(p1, p2) => UntypedForm::BiImpl(
-            Box::new(trans_form(*p1, vars)),
-            Box::new(trans_form(*p2, vars)),
-        ),
-        UntypedForm::Forall(_, _) => todo!(),
-        UntypedForm::Exist(_, _) => todo!(),
-        UntypedForm::ForG(x, ags, p) => UntypedForm::ForG(
-            meta::Agent(x),
-            ags.into_iter().map(meta::Agent).collect(),
-            Box::new(trans_form(*p, vars)),
-        ),
-        UntypedForm::K(ag, p) => UntypedForm::K(
-            UntypedRef(meta::Agent(ag.0)),
-            Box::new(trans_form(*p, vars)),
-        ),
-        UntypedForm::CK(ags, p) => UntypedForm::CK(
-            ags.into_iter()
-                .map(|ag| UntypedRef(meta::Agent(ag.0)))
-                .collect(),
-            Box::new(trans_form(*p, vars)),
-        ),
-        UntypedForm::DK(ags, p) => UntypedForm::DK(
-            ags.into_iter()
-                .map(|ag| UntypedRef(meta::Agent(ag.0)))
-                .collect(),
-            Box::new(trans_form(*p, vars)),
-        ),
-    }
+    p.map(meta::Agent, |b| {
+        meta::LocalTarget::Local(*vars.get(&b.0.value).unwrap())
+    })
 }
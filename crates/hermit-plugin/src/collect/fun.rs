@@ -10,12 +10,13 @@ use rustc_middle::{
         Body, Local, Statement, StatementKind, Terminator, TerminatorKind, VarDebugInfo,
         VarDebugInfoContents,
     },
-    ty::TyCtxt,
+    ty::{Instance, TyCtxt},
 };
-use rustc_span::def_id::LocalDefId;
+use rustc_span::def_id::{LocalDefId, CRATE_DEF_ID};
 use rustc_utils::mir::{borrowck_facts, location_or_arg::LocationOrArg};
 
 use either::Either;
+use hermit_core::Environment;
 use itertools::Itertools;
 
 use crate::meta;
@@ -68,9 +69,12 @@ impl<'tcx> FunCollector<'tcx> {
 
         // compute the forward dependencies for each location.
         let flows = self.collect_flows(locs.iter().map(|(_, loc)| *loc));
+        let sinks = self.collect_sinks();
 
         // process the attributes.
         let mut agents = Vec::new();
+        let mut groups = Vec::new();
+        let mut observers = Vec::new();
         let mut haves = Vec::new();
         let mut ensures = Vec::new();
         let mut forgets = Vec::new();
@@ -78,6 +82,8 @@ impl<'tcx> FunCollector<'tcx> {
         for attr in attrs {
             match attr {
                 AttrInfo::Agent(attr) => agents.push(attr),
+                AttrInfo::Group(attr) => groups.push(attr),
+                AttrInfo::Observers(attr) => observers.push(attr),
                 AttrInfo::Have(attr) => haves.push(AttrInfo::have_to_meta(attr, &locs)),
                 AttrInfo::Ensure(attr) => ensures.push(AttrInfo::ensure_to_meta(attr, &locs)),
                 AttrInfo::Forget(attr) => forgets.push(AttrInfo::forget_to_meta(attr, &locs)),
@@ -87,15 +93,32 @@ impl<'tcx> FunCollector<'tcx> {
         (
             meta::FunctionId(self.def_id().into()),
             meta::Function {
+                modules: self.modules(),
                 agents,
+                groups,
+                observers,
                 haves,
                 ensures,
                 forgets,
                 flows,
+                sinks,
+                names: self.local_names(),
             },
         )
     }
 
+    /// The modules enclosing the function, outermost first.
+    fn modules(&self) -> Vec<meta::ModuleId> {
+        let mut modules = Vec::new();
+        let mut id = self.def_id();
+        while id != CRATE_DEF_ID {
+            id = self.tcx.parent_module_from_def_id(id).to_local_def_id();
+            modules.push(meta::ModuleId(id.to_def_id()));
+        }
+        modules.reverse();
+        modules
+    }
+
     // Collect the attributes.
     fn collect_attrs(&self) -> Vec<AttrInfo> {
         self.attrs()
@@ -143,6 +166,39 @@ impl<'tcx> FunCollector<'tcx> {
             })
     }
 
+    /// Collect the calls to sinks of the standard library, which announce each of their arguments
+    /// to an environment agent.
+    fn collect_sinks(&self) -> BTreeMap<meta::Call, Environment> {
+        let param_env = self.tcx.param_env(self.def_id());
+        self.body()
+            .basic_blocks
+            .iter()
+            .filter_map(|block| match &block.terminator().kind {
+                TerminatorKind::Call { func, args, .. } => {
+                    let (id, generics) = func.const_fn_def()?;
+
+                    // resolve trait methods to their impl, as in `<TcpStream as Write>::write`.
+                    let callee = match Instance::resolve(self.tcx, param_env, id, generics) {
+                        Ok(Some(instance)) => instance.def_id(),
+                        _ => id,
+                    };
+                    let env = Environment::of_sink(&self.tcx.def_path_str(callee))?;
+
+                    let calls = (0..args.len()).map(move |idx| {
+                        let call = meta::Call {
+                            fun: meta::FunctionId(id),
+                            idx,
+                        };
+                        (call, env)
+                    });
+                    Some(calls)
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
     /// Extract the set of calls from `deps`.
     fn targets_of_deps(
         &self,
@@ -234,6 +290,43 @@ impl<'tcx> FunCollector<'tcx> {
             .collect()
     }
 
+    /// Name each local by its source variable, or else by the expression it holds and where, if
+    /// that fits on a line. Locals that share a name, as those of a macro, keep their MIR local.
+    fn local_names(&self) -> BTreeMap<Local, String> {
+        let source_map = self.tcx.sess.source_map();
+        let vars: BTreeMap<_, _> = self
+            .var_locations()
+            .into_iter()
+            .map(|(name, loc)| (loc.0, format!("`{name}`")))
+            .collect();
+        let exprs = self
+            .body()
+            .local_decls
+            .iter_enumerated()
+            .filter(|(local, _)| !vars.contains_key(local))
+            .filter_map(|(local, decl)| {
+                let span = decl.source_info.span.source_callsite();
+                let snippet = source_map.span_to_snippet(span).ok()?;
+                if snippet.is_empty() || snippet.contains('\n') {
+                    return None;
+                }
+                let start = source_map.lookup_char_pos(span.lo());
+                let name = format!("`{snippet}` (at {}:{})", start.line, start.col.0 + 1);
+                Some((local, name))
+            })
+            .collect::<Vec<_>>();
+
+        let names: Vec<_> = vars.into_iter().chain(exprs).collect();
+        let counts = names.iter().map(|(_, name)| name).counts();
+        names
+            .iter()
+            .map(|(local, name)| match counts[name] {
+                1 => (*local, name.clone()),
+                _ => (*local, format!("{name} as {local:?}")),
+            })
+            .collect()
+    }
+
     fn is_arg_location(&self, loc: &meta::FunctionLocation) -> bool {
         loc.0.as_usize() <= self.body().arg_count
     }
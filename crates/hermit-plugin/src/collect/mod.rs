@@ -4,12 +4,15 @@ mod fun;
 mod localflow;
 
 use std::collections::BTreeMap;
+use std::iter;
 
 use rustc_hir::{BodyId, ItemKind};
 use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::CRATE_DEF_ID;
 
 use crate::meta;
 
+use attr::{AttrCollector, AttrInfo};
 use fun::FunCollector;
 
 pub struct Collector<'tcx> {
@@ -23,7 +26,32 @@ impl<'tcx> Collector<'tcx> {
 
     pub fn collect(&self) -> meta::Meta {
         let funs = self.collect_fns();
-        meta::Meta { funs }
+        let groups = self.collect_groups();
+        meta::Meta { funs, groups }
+    }
+
+    /// Collect the groups declared on each module, including the crate root.
+    fn collect_groups(&self) -> BTreeMap<meta::ModuleId, Vec<meta::GroupAnn>> {
+        let hir = self.tcx.hir();
+        let root = (CRATE_DEF_ID, hir.krate_attrs());
+        let mods = hir
+            .items()
+            .filter(|&id| matches!(hir.item(id).kind, ItemKind::Mod(_)))
+            .map(|id| (id.owner_id.def_id, hir.attrs(id.hir_id())));
+
+        iter::once(root)
+            .chain(mods)
+            .map(|(id, attrs)| {
+                let groups = attrs
+                    .iter()
+                    .filter_map(|attr| match AttrCollector::new(attr).collect()? {
+                        AttrInfo::Group(group) => Some(group),
+                        _ => None,
+                    })
+                    .collect();
+                (meta::ModuleId(id.to_def_id()), groups)
+            })
+            .collect()
     }
 
     fn collect_fns(&self) -> BTreeMap<meta::FunctionId, meta::Function> {
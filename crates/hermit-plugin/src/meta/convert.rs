@@ -1,40 +1,107 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use hermit_core::UntypedMeta;
+use hermit_core::semantics::Flow;
+use hermit_core::{Principal, UntypedForget, UntypedMeta};
 
-use super::{Agent, Function, GlobalLocation, Meta};
+use super::{Agent, Function, FunctionId, LocalTarget, Meta};
 
 impl Meta {
-    pub fn untyped_meta_flow(&self) -> (UntypedMeta<Agent, GlobalLocation>, GlobalFlow) {
-        // let mut owners = BTreeMap::new();
-        // let mut haves = Vec::new();
-        // let mut ensures = Vec::new();
-        // let mut forgets = Vec::new();
-
-        // for (id, fun) in &self.funs {
-        // let Function {
-        // agents,
-        // haves,
-        // ensures,
-        // forgets,
-        // flows,
-        // } = fun;
-        // }
-
-        // (
-        // UntypedMeta {
-        // owners,
-        // haves,
-        // ensures,
-        // forgets,
-        // },
-        // todo!(),
-        // )
-
-        todo!()
+    /// The agents that own the data of the function `id`, or the default agent if it has no
+    /// `agent` annotation or is not part of the program.
+    fn principals(&self, id: &FunctionId) -> BTreeSet<Principal<Agent>> {
+        let agents: BTreeSet<_> = self
+            .funs
+            .get(id)
+            .into_iter()
+            .flat_map(|fun| &fun.agents)
+            .flat_map(|ann| ann.names.iter().cloned().map(Principal::Named))
+            .collect();
+
+        if agents.is_empty() {
+            BTreeSet::from([Principal::Default])
+        } else {
+            agents
+        }
+    }
+
+    /// The agents that see when data reaches the function `id`, besides its own, or `None` if
+    /// every agent does.
+    fn observers(&self, id: &FunctionId) -> Option<BTreeSet<Principal<Agent>>> {
+        let fun = self.funs.get(id)?;
+        if fun.observers.is_empty() {
+            return None;
+        }
+        let names = fun.observers.iter().flat_map(|ann| &ann.names);
+        Some(names.cloned().map(Principal::Named).collect())
+    }
+
+    /// The metadata for checking the assertions of `fun`, whose locals are owned by its agents
+    /// and whose calls are owned by the agents of the called function, as well as the outside
+    /// world for calls to sinks. The observers of each location are those of the function that
+    /// owns it.
+    pub fn untyped_meta(&self, id: &FunctionId, fun: &Function) -> UntypedMeta<Agent, LocalTarget> {
+        let haves: Vec<_> = fun.haves.iter().map(|have| have.form.clone()).collect();
+        let ensures: Vec<_> = fun
+            .ensures
+            .iter()
+            .map(|ensure| ensure.form.clone())
+            .collect();
+        let forgets: Vec<_> = fun
+            .forgets
+            .iter()
+            .map(|forget| UntypedForget::new(forget.subject, forget.dependencies.clone()))
+            .collect();
+
+        let locs: BTreeSet<_> = haves
+            .iter()
+            .chain(&ensures)
+            .flat_map(|form| form.vocab())
+            .chain(forgets.iter().map(|forget| &forget.subject))
+            .chain(forgets.iter().flat_map(|forget| &forget.dependencies))
+            .chain(
+                fun.flows
+                    .iter()
+                    .flat_map(|(loc, deps)| [loc].into_iter().chain(deps)),
+            )
+            .copied()
+            .collect();
+        let owner = |loc: &LocalTarget| match loc {
+            LocalTarget::Local(_) => *id,
+            LocalTarget::Call(call) => call.fun,
+        };
+        let owners = locs
+            .iter()
+            .map(|loc| (*loc, self.principals(&owner(loc))))
+            .collect();
+
+        let meta =
+            UntypedMeta::new(owners, haves, ensures, forgets).with_groups(self.groups_of(fun));
+        let meta = locs
+            .iter()
+            .fold(meta, |meta, loc| match self.observers(&owner(loc)) {
+                Some(observers) => meta.with_observers(*loc, observers),
+                None => meta,
+            });
+        fun.sinks.iter().fold(meta, |meta, (call, env)| {
+            meta.with_sink(LocalTarget::Call(*call), *env)
+        })
     }
 }
 
-pub struct LocalFLow {}
+/// The flow between the locations of a function.
+#[derive(Debug, Clone, Copy)]
+pub struct LocalFlow<'f>(&'f BTreeMap<LocalTarget, Vec<LocalTarget>>);
 
-pub struct GlobalFlow {}
+impl<'f> LocalFlow<'f> {
+    pub fn new(flows: &'f BTreeMap<LocalTarget, Vec<LocalTarget>>) -> Self {
+        Self(flows)
+    }
+}
+
+impl<'f> Flow for LocalFlow<'f> {
+    type Location = &'f LocalTarget;
+
+    fn forward(&self, loc: Self::Location) -> impl Iterator<Item = Self::Location> {
+        self.0.get(loc).into_iter().flatten()
+    }
+}
@@ -1,34 +1,69 @@
 pub mod convert;
 
 use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
 
-use rustc_middle::mir::Local;
+use rustc_middle::{mir::Local, ty::TyCtxt};
 use rustc_span::def_id::DefId;
 
-use hermit_core::UntypedForm;
+use hermit_core::{Environment, UntypedForm};
 
-pub use hermit_syntax::{attribute::AgentMeta as AgentsAnn, lang::Agent};
+pub use hermit_syntax::{
+    attribute::{AgentMeta as AgentsAnn, GroupMeta as GroupAnn, ObserversMeta as ObserversAnn},
+    lang::Agent,
+};
 
 /// The metadata of a program.
 #[derive(Debug, Clone)]
 pub struct Meta {
     pub funs: BTreeMap<FunctionId, Function>,
+    /// The groups declared on each module, including the crate root, which apply to the
+    /// functions inside it.
+    pub groups: BTreeMap<ModuleId, Vec<GroupAnn>>,
+}
+
+impl Meta {
+    /// The members of each group in scope for `fun`, where a group declared on an inner module
+    /// or on the function replaces an outer one of the same name.
+    pub fn groups_of(&self, fun: &Function) -> BTreeMap<Agent, Vec<Agent>> {
+        fun.modules
+            .iter()
+            .filter_map(|module| self.groups.get(module))
+            .flatten()
+            .chain(&fun.groups)
+            .map(|group| (group.name.clone(), group.members.clone()))
+            .collect()
+    }
 }
 
 /// The identifier for a function.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy)]
 pub struct FunctionId(pub DefId);
 
+/// The identifier for a module.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy)]
+pub struct ModuleId(pub DefId);
+
 /// The metadata of a function.
 #[derive(Debug, Clone)]
 pub struct Function {
+    /// The modules enclosing the function, outermost first.
+    pub modules: Vec<ModuleId>,
     pub agents: Vec<AgentsAnn>,
+    pub groups: Vec<GroupAnn>,
+    /// The agents that see when data reaches the function, besides its own, if any are declared.
+    pub observers: Vec<ObserversAnn>,
     pub haves: Vec<HaveAnn>,
     pub ensures: Vec<EnsureAnn>,
     pub forgets: Vec<ForgetAnn>,
 
     /// The map of important locations to their dependent calls.
     pub flows: BTreeMap<LocalTarget, Vec<LocalTarget>>,
+    /// The calls that announce their arguments to the outside world.
+    pub sinks: BTreeMap<Call, Environment>,
+    /// How each local is shown at the source level, i.e. by its variable or by the expression it
+    /// holds and where.
+    pub names: BTreeMap<Local, String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy)]
@@ -50,6 +85,35 @@ pub struct Call {
     pub idx: usize,
 }
 
+impl Display for LocalTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalTarget::Local(FunctionLocation(local)) => write!(f, "{local:?}"),
+            LocalTarget::Call(Call { fun, idx }) => {
+                write!(f, "argument {idx} of the call to {:?}", fun.0)
+            }
+        }
+    }
+}
+
+impl LocalTarget {
+    /// Format the target at the source level, with the names of the locals of `fun`.
+    pub fn describe(&self, tcx: TyCtxt<'_>, fun: &Function) -> String {
+        match self {
+            LocalTarget::Local(FunctionLocation(local)) => match fun.names.get(local) {
+                Some(name) => name.clone(),
+                None => format!("{local:?}"),
+            },
+            LocalTarget::Call(Call { fun, idx }) => {
+                format!(
+                    "argument {idx} of the call to `{}`",
+                    tcx.def_path_str(fun.0)
+                )
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HaveAnn {
     pub form: UntypedForm<Agent, LocalTarget>,
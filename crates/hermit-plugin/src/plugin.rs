@@ -3,6 +3,7 @@ use std::env;
 use std::process::Command;
 
 use clap::Parser;
+use hermit_core::semantics::Backend;
 use rustc_plugin::{CrateFilter, RustcPlugin, RustcPluginArgs, Utf8Path};
 use rustc_utils::mir::borrowck_facts;
 use serde::{Deserialize, Serialize};
@@ -13,17 +14,43 @@ pub struct HermitPlugin;
 
 #[derive(Debug, Parser, Serialize, Deserialize)]
 pub struct HermitPluginArgs {
+    /// The checker for knowledge structures.
+    #[clap(long, default_value = "bdd", value_parser = Backend::select)]
+    backend: Backend,
+
+    /// Report the metrics of the formulas of each function.
+    #[clap(long)]
+    metrics: bool,
+
+    /// Warn about, rather than reject, positive knowledge assertions in `ensure` clauses, which
+    /// are unsound under over-approximate information flow.
+    #[clap(long)]
+    allow_positive_knowledge: bool,
+
     #[clap(last = true)]
     cargo_args: Vec<String>,
 }
 
 #[derive(Debug)]
-pub struct HermitPluginConfig {}
+pub struct HermitPluginConfig {
+    pub backend: Backend,
+    pub metrics: bool,
+    pub allow_positive_knowledge: bool,
+}
 
 impl From<HermitPluginArgs> for HermitPluginConfig {
     fn from(value: HermitPluginArgs) -> Self {
-        let HermitPluginArgs { cargo_args: _ } = value;
-        Self {}
+        let HermitPluginArgs {
+            backend,
+            metrics,
+            allow_positive_knowledge,
+            cargo_args: _,
+        } = value;
+        Self {
+            backend,
+            metrics,
+            allow_positive_knowledge,
+        }
     }
 }
 
@@ -81,7 +108,7 @@ impl rustc_driver::Callbacks for HermitCallbacks {
         queries
             .global_ctxt()
             .unwrap()
-            .enter(|tcx| analyse::analyse(tcx));
+            .enter(|tcx| analyse::analyse(tcx, &self.config));
 
         rustc_driver::Compilation::Stop
     }
@@ -34,7 +34,7 @@ pub struct Meta {
 }
 
 impl Meta {
-    pub const KIND: &'static str = "have";
+    pub const KIND: &'static str = "ensure";
 }
 
 impl Parse for Meta {
@@ -0,0 +1,68 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde::{Deserialize, Serialize};
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    ItemFn, ItemMod, Token,
+};
+
+use crate::lang::Agent;
+use crate::TOOL;
+
+use super::{Encode, ItemAttribute};
+
+pub struct Attribute;
+
+impl Attribute {
+    fn annotate(&self, args: Meta, item: impl quote::ToTokens) -> TokenStream {
+        let tool = TOOL.ident();
+        let kind = Ident::new(Meta::KIND, Span::call_site());
+        let args = args.encode();
+
+        quote! {
+            #[#tool::#kind(#args)]
+            #item
+        }
+    }
+}
+
+impl ItemAttribute for Attribute {
+    type Args = Meta;
+
+    /// Declare the group for the function.
+    fn impl_fn(&self, args: Self::Args, item: ItemFn) -> TokenStream {
+        self.annotate(args, item)
+    }
+
+    /// Declare the group for every function of the module.
+    fn impl_mod(&self, args: Self::Args, item: ItemMod) -> TokenStream {
+        self.annotate(args, item)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Meta {
+    pub name: Agent,
+    pub members: Vec<Agent>,
+}
+
+impl Meta {
+    pub const KIND: &'static str = "group";
+}
+
+impl Parse for Meta {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // (ex:) backend = [db, cache, queue]
+        let name = input.parse()?;
+        let _ = input.parse::<Token![=]>()?;
+        let inner;
+        let _ = bracketed!(inner in input);
+        let members = Punctuated::<Agent, Token![,]>::parse_terminated(&inner)?
+            .into_iter()
+            .collect();
+
+        Ok(Self { name, members })
+    }
+}
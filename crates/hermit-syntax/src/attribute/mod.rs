@@ -1,7 +1,9 @@
 pub mod agent;
 pub mod ensure;
 pub mod forget;
+pub mod group;
 pub mod have;
+pub mod observers;
 
 use paste::paste;
 use proc_macro2::TokenStream;
@@ -132,4 +134,6 @@ pub use crate::lang::{Agent, Form, Ident};
 pub use agent::Meta as AgentMeta;
 pub use ensure::Meta as EnsureMeta;
 pub use forget::Meta as ForgetMeta;
+pub use group::Meta as GroupMeta;
 pub use have::Meta as HaveMeta;
+pub use observers::Meta as ObserversMeta;
@@ -1,5 +1,6 @@
 mod parse;
 
+use std::fmt::{self, Display, Formatter};
 use std::ops::Range;
 
 use hermit_core::UntypedForm;
@@ -30,7 +31,7 @@ pub struct Agent(pub Ident);
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Variable(pub Ident);
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Form(pub UntypedForm<Ident, Ident>);
 
 impl Default for Form {
@@ -39,6 +40,44 @@ impl Default for Form {
     }
 }
 
+impl Display for Ident {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.value.fmt(f)
+    }
+}
+
+/// The name, by which formulas refer to built-in agents.
+impl AsRef<str> for Ident {
+    fn as_ref(&self) -> &str {
+        &self.0.value
+    }
+}
+
+impl AsRef<str> for Agent {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl Display for Agent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Format in the surface syntax accepted by the parser.
+impl Display for Form {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl<T> PartialEq for Spanned<T>
 where
     T: PartialEq,
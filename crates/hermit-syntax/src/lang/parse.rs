@@ -1,11 +1,11 @@
 use std::mem;
 
-use hermit_core::{UntypedForm, UntypedRef};
+use hermit_core::{AgentSet, UntypedForm, UntypedRef};
 use paste::paste;
 use syn::{
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
-    token::Paren,
+    token::{Bracket, Paren},
     LitBool, Token,
 };
 
@@ -69,6 +69,10 @@ fn parse(input: ParseStream, min_bp: BP) -> Result<UntypedForm<Ident, Ident>> {
         parse_agents(input)?
     } else if lookahead.peek(kw::K) {
         parse_know(input)?
+    } else if lookahead.peek(kw::Kw) && input.peek2(Bracket) {
+        parse_know_whether(input)?
+    } else if lookahead.peek(kw::B) && input.peek2(Bracket) {
+        parse_believe(input)?
     } else if lookahead.peek(kw::C) {
         parse_common(input)?
     } else if lookahead.peek(kw::D) {
@@ -81,32 +85,21 @@ fn parse(input: ParseStream, min_bp: BP) -> Result<UntypedForm<Ident, Ident>> {
 
     loop {
         let lookahead = input.lookahead1();
-        lhs = if lookahead.peek(Token![->]) {
-            match parse_impl(input, min_bp, mem::take(&mut lhs))? {
-                Some(lhs) => lhs,
-                None => break,
-            }
+        let extended = if lookahead.peek(Token![->]) {
+            parse_impl(input, min_bp, &mut lhs)?
         } else if lookahead.peek(Token![&&]) {
-            match parse_conj(input, min_bp, mem::take(&mut lhs))? {
-                Some(lhs) => lhs,
-                None => break,
-            }
+            parse_conj(input, min_bp, &mut lhs)?
         } else if lookahead.peek(Token![||]) {
-            match parse_disj(input, min_bp, mem::take(&mut lhs))? {
-                Some(lhs) => lhs,
-                None => break,
-            }
+            parse_disj(input, min_bp, &mut lhs)?
         } else if lookahead.peek(Token![^]) {
-            match parse_xor(input, min_bp, mem::take(&mut lhs))? {
-                Some(lhs) => lhs,
-                None => break,
-            }
+            parse_xor(input, min_bp, &mut lhs)?
         } else if lookahead.peek(Token![==]) {
-            match parse_biimpl(input, min_bp, mem::take(&mut lhs))? {
-                Some(lhs) => lhs,
-                None => break,
-            }
+            parse_biimpl(input, min_bp, &mut lhs)?
         } else {
+            false
+        };
+
+        if !extended {
             break;
         }
     }
@@ -154,6 +147,7 @@ fn parse_exist(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
 
 fn parse_agents(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
     // agents <ag> : <form>
+    // agents <ag> in * : <form>
     // agents <ag> in <ag>, <ag>, ... : <form>
     let _ = input.parse::<kw::agents>()?;
     let rf = input.parse()?;
@@ -161,11 +155,16 @@ fn parse_agents(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
     let lookahead = input.lookahead1();
     let ags = if lookahead.peek(Token![in]) {
         let _ = input.parse::<Token![in]>()?;
-        Punctuated::<_, Token![,]>::parse_separated_nonempty(input)?
-            .into_iter()
-            .collect()
+        if input.peek(Token![*]) {
+            // every agent, including the default agent of unannotated functions.
+            let _ = input.parse::<Token![*]>()?;
+            AgentSet::All { default: true }
+        } else {
+            let ags = Punctuated::<_, Token![,]>::parse_separated_nonempty(input)?;
+            AgentSet::Of(ags.into_iter().collect())
+        }
     } else {
-        vec![]
+        AgentSet::All { default: false }
     };
 
     let _ = input.parse::<Token![:]>()?;
@@ -186,6 +185,32 @@ fn parse_know(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
     Ok(UntypedForm::K(UntypedRef(ag), Box::new(form)))
 }
 
+fn parse_know_whether(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
+    // Kw[<ag> : <form>]
+    let _ = input.parse::<kw::Kw>()?;
+    let inner;
+    let _ = syn::bracketed!(inner in input);
+
+    let ag = inner.parse::<Ident>()?;
+    let _ = inner.parse::<Token![:]>()?;
+    let form = parse(&inner, BP::MIN)?;
+
+    Ok(UntypedForm::Kw(UntypedRef(ag), Box::new(form)))
+}
+
+fn parse_believe(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
+    // B[<ag> : <form>]
+    let _ = input.parse::<kw::B>()?;
+    let inner;
+    let _ = syn::bracketed!(inner in input);
+
+    let ag = inner.parse::<Ident>()?;
+    let _ = inner.parse::<Token![:]>()?;
+    let form = parse(&inner, BP::MIN)?;
+
+    Ok(UntypedForm::B(UntypedRef(ag), Box::new(form)))
+}
+
 fn parse_common(input: ParseStream) -> Result<UntypedForm<Ident, Ident>> {
     // C[<ag>, <ag>, ... : <form>]
     let _ = input.parse::<kw::C>()?;
@@ -241,15 +266,17 @@ macro_rules! parse_bin_rhs {
             fn [<parse_ $name>](
                 input: ParseStream,
                 min_bp: BP,
-                lhs: UntypedForm<Ident, Ident>,
-            ) -> Result<Option<UntypedForm<Ident, Ident>>> {
+                lhs: &mut UntypedForm<Ident, Ident>,
+            ) -> Result<bool> {
                 let (lbp, rbp) = <Token![$token] as InfixBp>::infix_bp();
                 if lbp < min_bp {
-                    return Ok(None);
+                    return Ok(false);
                 }
+                let _ = input.parse::<Token![$token]>()?;
 
                 let rhs = parse(input, rbp)?;
-                Ok(Some(UntypedForm::$variant(Box::new(lhs), Box::new(rhs))))
+                *lhs = UntypedForm::$variant(Box::new(mem::take(lhs)), Box::new(rhs));
+                Ok(true)
             }
         }
     };
@@ -308,11 +335,52 @@ impl InfixBp for Token![==] {
     }
 }
 
+/// `Kw` and `B` are keywords only when followed by brackets, and otherwise variables.
 mod kw {
     syn::custom_keyword!(forall);
     syn::custom_keyword!(exist);
     syn::custom_keyword!(agents);
     syn::custom_keyword!(K);
+    syn::custom_keyword!(Kw);
+    syn::custom_keyword!(B);
     syn::custom_keyword!(C);
     syn::custom_keyword!(D);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip(src: &str) {
+        let form: Form = syn::parse_str(src).expect(src);
+        let printed = form.to_string();
+        let reparsed: Form = syn::parse_str(&printed).unwrap();
+        assert_eq!(form, reparsed, "{src} printed as {printed}");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip("K[a: pwd]");
+        round_trip("agents a: !Kw[a: flag]");
+        round_trip("B[log: x] && !K[log: x]");
+        round_trip("C[a, b: x] && D[a, b: !y]");
+        round_trip("agents a in b, c: !K[a: pwd]");
+        round_trip("agents a in *: !K[a: pwd]");
+        round_trip("agents a: K[a: x] -> K[a: y]");
+        round_trip("(!x) && y || (z ^ true)");
+        round_trip("(x -> y) -> z == (w == v)");
+        round_trip("forall x, y: (exist z: x && z) && !y");
+        round_trip("!(x && y) == false");
+    }
+
+    #[test]
+    fn test_keyword_variables() {
+        for src in ["B", "Kw"] {
+            let form: Form = syn::parse_str(src).unwrap();
+            assert!(matches!(form.0, UntypedForm::Prop(_)));
+        }
+
+        round_trip("B && !Kw");
+        round_trip("K[a: B] -> Kw[a: Kw] && B[a: B]");
+    }
+}
@@ -1,37 +1,137 @@
-use std::env;
-use std::ffi::{c_char, CString};
+use std::error::Error;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::fmt::{self, Display, Formatter};
+use std::ptr;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
 
 mod raw;
+pub mod schema;
 
+pub use schema::{Request, Verdict};
+
+/// The process-global Haskell runtime, initialized at most once.
+static RUNTIME: OnceLock<Result<Haskell, HaskellError>> = OnceLock::new();
+
+/// A request to the runtime thread, with where to send the JSON-encoded verdict.
+type Job = (CString, Sender<Vec<u8>>);
+
+/// A handle to the Haskell runtime.
+///
+/// The runtime lives on a dedicated thread, which makes every call into Haskell, until the
+/// process exits. It is never shut down, so it never needs to be initialized again.
+#[derive(Debug)]
 pub struct Haskell {
-    inner: HaskellInternal,
+    jobs: Mutex<Sender<Job>>,
 }
 
-impl Haskell {
-    pub fn init(args: env::Args) -> Self {
-        Self {
-            inner: HaskellInternal::init(args),
+/// An error using the Haskell runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HaskellError {
+    /// An argument for the runtime contains a NUL byte.
+    Argument(String),
+    /// The runtime thread could not be started.
+    Spawn(String),
+    /// The runtime thread has stopped, after a panic.
+    Stopped,
+    /// SMCDEL could not check the request.
+    Check(String),
+}
+
+impl Display for HaskellError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            HaskellError::Argument(arg) => write!(f, "runtime argument {arg:?} contains NUL"),
+            HaskellError::Spawn(err) => write!(f, "failed to start the Haskell runtime: {err}"),
+            HaskellError::Stopped => write!(f, "the Haskell runtime has stopped"),
+            HaskellError::Check(err) => write!(f, "SMCDEL failed to check the request: {err}"),
         }
     }
 }
 
-struct HaskellInternal;
+impl Error for HaskellError {}
 
-impl HaskellInternal {
-    pub fn init(args: env::Args) -> Self {
-        let mut args = args
-            .map(|arg| CString::new(arg).unwrap())
-            .map(|arg| arg.into_raw())
-            .collect::<Vec<*mut c_char>>();
-        unsafe { raw::hs_init(args.len() as *mut i32, &mut args.as_mut_ptr()) }
-        Self
+impl Haskell {
+    /// Get the runtime, initializing it with the program arguments `args` if this is the first
+    /// use. Later calls ignore `args`.
+    pub fn init(args: impl IntoIterator<Item = String>) -> Result<&'static Self, HaskellError> {
+        RUNTIME
+            .get_or_init(|| Self::start(args.into_iter().collect()))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Get the runtime, initializing it without arguments if this is the first use.
+    pub fn get() -> Result<&'static Self, HaskellError> {
+        Self::init(["smcdel".to_string()])
     }
-}
 
-impl Drop for HaskellInternal {
-    fn drop(&mut self) {
-        unsafe {
-            raw::hs_exit();
+    /// Start the runtime thread, which initializes the runtime before serving any jobs.
+    fn start(args: Vec<String>) -> Result<Self, HaskellError> {
+        let args = args
+            .into_iter()
+            .map(|arg| CString::new(arg).map_err(|err| HaskellError::Argument(err.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (jobs, queue) = mpsc::channel::<Job>();
+        thread::Builder::new()
+            .name("haskell".to_string())
+            .spawn(move || {
+                // the runtime may keep `argv`, which lives as long as the thread.
+                let mut argv: Vec<*mut c_char> = args
+                    .iter()
+                    .map(|arg| arg.as_ptr() as *mut c_char)
+                    .chain([ptr::null_mut()])
+                    .collect();
+                let mut argc = args.len() as c_int;
+                let mut argv_ptr = argv.as_mut_ptr();
+                // SAFETY: this is the only call to `hs_init`, and `argv` is NULL-terminated.
+                unsafe { raw::hs_init(&mut argc, &mut argv_ptr) };
+
+                for (input, reply) in queue {
+                    // the requester may have given up, in which case no one needs the verdict.
+                    let _ = reply.send(call_check(&input));
+                }
+            })
+            .map_err(|err| HaskellError::Spawn(err.to_string()))?;
+
+        Ok(Self {
+            jobs: Mutex::new(jobs),
+        })
+    }
+
+    /// Check `request` with SMCDEL, on the runtime thread.
+    pub fn check(&self, request: &Request) -> Result<Verdict, HaskellError> {
+        let input = serde_json::to_string(request).expect("failed to serialize request");
+        let input = CString::new(input).expect("JSON contains no NUL bytes");
+
+        let (reply, verdict) = mpsc::channel();
+        self.jobs
+            .lock()
+            .map_err(|_| HaskellError::Stopped)?
+            .send((input, reply))
+            .map_err(|_| HaskellError::Stopped)?;
+        let output = verdict.recv().map_err(|_| HaskellError::Stopped)?;
+
+        match serde_json::from_slice(&output) {
+            Ok(Verdict::Error(err)) => Err(HaskellError::Check(err)),
+            Ok(verdict) => Ok(verdict),
+            Err(err) => Err(HaskellError::Check(format!("malformed verdict: {err}"))),
         }
     }
 }
+
+/// Call `smcdel_check` on a JSON-encoded request, returning the JSON-encoded verdict.
+///
+/// Must only be called on the runtime thread.
+fn call_check(input: &CStr) -> Vec<u8> {
+    // SAFETY: the runtime is initialized, `smcdel_check` does not retain its argument, and it
+    // returns a NUL-terminated string that is released exactly once by `smcdel_free`.
+    unsafe {
+        let output = raw::smcdel_check(input.as_ptr() as *mut _) as *mut c_char;
+        let bytes = CStr::from_ptr(output).to_bytes().to_vec();
+        raw::smcdel_free(output as *mut _);
+        bytes
+    }
+}
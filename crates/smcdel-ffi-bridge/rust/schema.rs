@@ -0,0 +1,109 @@
+//! The JSON schema shared with `Lib.hs`, in aeson's default encoding of its data types.
+
+use serde::{Deserialize, Serialize};
+
+/// An SMCDEL agent.
+pub type Agent = String;
+
+/// An SMCDEL proposition.
+pub type Prp = u32;
+
+/// A formula, as `FFIForm`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "tag", content = "contents")]
+pub enum Form {
+    #[serde(rename = "FFITop")]
+    Top,
+    #[serde(rename = "FFIBot")]
+    Bot,
+    #[serde(rename = "FFIPrpF")]
+    PrpF(Prp),
+    #[serde(rename = "FFINeg")]
+    Neg(Box<Form>),
+    #[serde(rename = "FFIConj")]
+    Conj(Vec<Form>),
+    #[serde(rename = "FFIDisj")]
+    Disj(Vec<Form>),
+    #[serde(rename = "FFIXor")]
+    Xor(Vec<Form>),
+    #[serde(rename = "FFIImpl")]
+    Impl(Box<Form>, Box<Form>),
+    #[serde(rename = "FFIEqui")]
+    Equi(Box<Form>, Box<Form>),
+    #[serde(rename = "FFIForall")]
+    Forall(Vec<Prp>, Box<Form>),
+    #[serde(rename = "FFIExists")]
+    Exists(Vec<Prp>, Box<Form>),
+    #[serde(rename = "FFIK")]
+    K(Agent, Box<Form>),
+    #[serde(rename = "FFICk")]
+    Ck(Vec<Agent>, Box<Form>),
+    #[serde(rename = "FFIDk")]
+    Dk(Vec<Agent>, Box<Form>),
+    #[serde(rename = "FFIKw")]
+    Kw(Agent, Box<Form>),
+    #[serde(rename = "FFICkw")]
+    Ckw(Vec<Agent>, Box<Form>),
+    #[serde(rename = "FFIDkw")]
+    Dkw(Vec<Agent>, Box<Form>),
+    #[serde(rename = "FFIPubAnnounce")]
+    PubAnnounce(Box<Form>, Box<Form>),
+    #[serde(rename = "FFIPubAnnounceW")]
+    PubAnnounceW(Box<Form>, Box<Form>),
+    #[serde(rename = "FFIAnnounce")]
+    Announce(Vec<Agent>, Box<Form>, Box<Form>),
+    #[serde(rename = "FFIAnnounceW")]
+    AnnounceW(Vec<Agent>, Box<Form>, Box<Form>),
+}
+
+/// A knowledge structure, as `FFIKnowStruct`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowStruct {
+    pub vocab: Vec<Prp>,
+    /// The law, interpreted over the structure without observations.
+    pub law: Form,
+    pub obs: Vec<(Agent, Vec<Prp>)>,
+}
+
+/// A formula to check on a knowledge structure, as `FFIRequest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Request {
+    pub structure: KnowStruct,
+    pub form: Form,
+}
+
+/// The outcome of a check, as `FFIVerdict`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "tag", content = "contents")]
+pub enum Verdict {
+    /// The formula is true at every state.
+    #[serde(rename = "FFIHolds")]
+    Holds,
+    /// The formula is false at the state with these true propositions.
+    #[serde(rename = "FFIFails")]
+    Fails(Vec<Prp>),
+    /// The request could not be checked.
+    #[serde(rename = "FFIError")]
+    Error(String),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_schema() {
+        let form = Form::Impl(
+            Box::new(Form::Top),
+            Box::new(Form::K("alice".to_string(), Box::new(Form::PrpF(1)))),
+        );
+        assert_eq!(
+            serde_json::to_string(&form).unwrap(),
+            r#"{"tag":"FFIImpl","contents":[{"tag":"FFITop"},{"tag":"FFIK","contents":["alice",{"tag":"FFIPrpF","contents":1}]}]}"#
+        );
+
+        let verdict: Verdict =
+            serde_json::from_str(r#"{"tag":"FFIFails","contents":[2,3]}"#).unwrap();
+        assert_eq!(verdict, Verdict::Fails(vec![2, 3]));
+    }
+}
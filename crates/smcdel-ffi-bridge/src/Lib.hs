@@ -4,10 +4,18 @@
 
 module Lib where
 
-import Data.Aeson (FromJSON, ToJSON)
+import Control.Exception (SomeException, evaluate, try)
+import Data.Aeson (FromJSON, ToJSON, eitherDecodeStrict, encode)
+import qualified Data.ByteString as BS
+import qualified Data.ByteString.Lazy as BSL
+import Data.Word (Word8)
 import Foreign.C (CString)
+import Foreign.Marshal.Alloc (free, mallocBytes)
+import Foreign.Marshal.Utils (copyBytes)
+import Foreign.Storable (pokeByteOff)
 import GHC.Generics (Generic)
 import SMCDEL.Language (Agent, Form (..), Prp (..))
+import SMCDEL.Symbolic.S5 (KnowStruct (..), State, boolBddOf, bddOf, whereViaBdd)
 
 data FFIForm
   = FFITop
@@ -43,7 +51,96 @@ instance FromJSON FFIForm
 
 instance ToJSON FFIForm
 
-foreign export ccall foo :: Int -> IO Int
+-- | A knowledge structure, whose law may contain modalities.
+data FFIKnowStruct = FFIKnowStruct
+  { vocab :: [Prp],
+    law :: FFIForm,
+    obs :: [(Agent, [Prp])]
+  }
+  deriving (Eq, Ord, Show, Generic)
+
+instance FromJSON FFIKnowStruct
+
+instance ToJSON FFIKnowStruct
+
+-- | A formula to check on a knowledge structure.
+data FFIRequest = FFIRequest
+  { structure :: FFIKnowStruct,
+    form :: FFIForm
+  }
+  deriving (Eq, Ord, Show, Generic)
+
+instance FromJSON FFIRequest
+
+instance ToJSON FFIRequest
+
+-- | The outcome of a check: the formula holds, fails at a state given by its true propositions,
+-- or could not be checked.
+data FFIVerdict
+  = FFIHolds
+  | FFIFails State
+  | FFIError String
+  deriving (Eq, Ord, Show, Generic)
+
+instance FromJSON FFIVerdict
+
+instance ToJSON FFIVerdict
+
+toForm :: FFIForm -> Form
+toForm FFITop = Top
+toForm FFIBot = Bot
+toForm (FFIPrpF p) = PrpF p
+toForm (FFINeg f) = Neg (toForm f)
+toForm (FFIConj fs) = Conj (map toForm fs)
+toForm (FFIDisj fs) = Disj (map toForm fs)
+toForm (FFIXor fs) = Xor (map toForm fs)
+toForm (FFIImpl f g) = Impl (toForm f) (toForm g)
+toForm (FFIEqui f g) = Equi (toForm f) (toForm g)
+toForm (FFIForall ps f) = Forall ps (toForm f)
+toForm (FFIExists ps f) = Exists ps (toForm f)
+toForm (FFIK a f) = K a (toForm f)
+toForm (FFICk ags f) = Ck ags (toForm f)
+toForm (FFIDk ags f) = Dk ags (toForm f)
+toForm (FFIKw a f) = Kw a (toForm f)
+toForm (FFICkw ags f) = Ckw ags (toForm f)
+toForm (FFIDkw ags f) = Dkw ags (toForm f)
+toForm (FFIPubAnnounce f g) = PubAnnounce (toForm f) (toForm g)
+toForm (FFIPubAnnounceW f g) = PubAnnounceW (toForm f) (toForm g)
+toForm (FFIAnnounce ags f g) = Announce ags (toForm f) (toForm g)
+toForm (FFIAnnounceW ags f g) = AnnounceW ags (toForm f) (toForm g)
+
+-- | Check that the formula is true at every state of the structure.
+--
+-- The law is interpreted over the structure without observations.
+check :: FFIRequest -> FFIVerdict
+check (FFIRequest (FFIKnowStruct v l o) f) =
+  let trivial = KnS v (boolBddOf Top) []
+      kns = KnS v (bddOf trivial (toForm l)) o
+   in case whereViaBdd kns (Neg (toForm f)) of
+        [] -> FFIHolds
+        (s : _) -> FFIFails s
+
+-- | Check a JSON-encoded 'FFIRequest', returning a JSON-encoded 'FFIVerdict'.
+--
+-- The result is allocated with @malloc@ and must be released with 'smcdel_free'.
+foreign export ccall smcdel_check :: CString -> IO CString
+
+smcdel_check :: CString -> IO CString
+smcdel_check input = do
+  request <- BS.packCString input
+  let verdict = either FFIError check (eitherDecodeStrict request)
+  result <- try (evaluate (BSL.toStrict (encode verdict)))
+  let output = case result of
+        Right bytes -> bytes
+        Left err -> BSL.toStrict (encode (FFIError (show (err :: SomeException))))
+  BS.useAsCStringLen output $ \(ptr, len) -> do
+    out <- mallocBytes (len + 1)
+    copyBytes out ptr len
+    pokeByteOff out len (0 :: Word8)
+    return out
+
+-- | Release a string returned by 'smcdel_check'.
+foreign export ccall smcdel_free :: CString -> IO ()
 
-foo :: Int -> IO Int
-foo = return
+smcdel_free :: CString -> IO ()
+smcdel_free = free